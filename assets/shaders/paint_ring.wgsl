@@ -0,0 +1,31 @@
+// Draw the remaining time of the timed paint as a ring emptying clockwise.
+
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+const PI: f32 = 3.14159265;
+const OUTER_RADIUS: f32 = 0.5;
+const INNER_RADIUS: f32 = 0.32;
+const EMPTY_ALPHA: f32 = 0.25;
+
+struct PaintRingMaterial {
+    color: vec4<f32>,
+    progress: f32,
+};
+
+@group(1) @binding(0) var<uniform> material: PaintRingMaterial;
+
+@fragment
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
+    let offset = in.uv - vec2(0.5);
+    let radius = length(offset);
+    if radius > OUTER_RADIUS || radius < INNER_RADIUS {
+        discard;
+    }
+
+    // Angle from the top of the ring, clockwise, in [0, 1)
+    let angle = fract(atan2(offset.x, -offset.y) / (2.0 * PI) + 1.0);
+    if angle < material.progress {
+        return material.color;
+    }
+    return vec4(material.color.rgb, material.color.a * EMPTY_ALPHA);
+}
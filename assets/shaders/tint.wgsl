@@ -0,0 +1,20 @@
+// Draw each color of the player tint as diagonal stripes.
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+const MAX_TINT_COLORS: u32 = 4u;
+const STRIPE_COUNT: f32 = 6.0;
+
+struct TintMaterial {
+    colors: array<vec4<f32>, MAX_TINT_COLORS>,
+    count: u32,
+};
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> material: TintMaterial;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let count = max(material.count, 1u);
+    let stripe = u32(floor((mesh.uv.x + mesh.uv.y) * STRIPE_COUNT));
+    return material.colors[stripe % count];
+}
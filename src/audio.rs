@@ -27,8 +27,9 @@ impl std::fmt::Display for VolumeType {
 }
 
 /// Taken and adapted from <https://github.com/benfrankel/pyri_new_jam/blob/main/src/core/audio.rs>
-#[derive(Resource, Reflect, Clone, Debug)]
+#[derive(Resource, Reflect, Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[reflect(Resource)]
+#[serde(default)]
 pub struct AudioSettings {
     pub master_volume: f32,
     pub sfx_volume: f32,
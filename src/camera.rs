@@ -3,7 +3,10 @@ use bevy::prelude::*;
 
 use bevy_modern_pixel_camera::prelude::*;
 
-use crate::ldtk::gridvania::{GridLevelSelection, LEVEL_SIZE};
+use crate::{
+    ldtk::gridvania::{GridLevelSelection, GridvaniaLevels, LEVEL_SIZE},
+    player::Player,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(PixelCameraPlugin);
@@ -33,21 +36,37 @@ fn spawn_camera(mut commands: Commands) {
 }
 
 /// Change the position of the camera to match the level position
+///
+/// In rooms larger than the screen, the camera follows the player within the
+/// room bounds.
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct FollowLevelSelection;
 
 fn update_camera_position(
     time: Res<Time>,
-    mut cameras: Query<&mut Transform, With<FollowLevelSelection>>,
+    mut cameras: Query<&mut Transform, (With<FollowLevelSelection>, Without<Player>)>,
     level_selection: Res<GridLevelSelection>,
+    levels: Option<Res<GridvaniaLevels>>,
+    player: Option<Single<&Transform, With<Player>>>,
 ) {
     // Smoothing factor: higher = faster movement
     // ~10.0 gives approximately 0.5 seconds to reach the target
     let smoothing = 10.0;
 
+    let target_position = match levels.as_ref().and_then(|l| l.room_at(level_selection.0)) {
+        Some(room) => {
+            // Positions of the camera keeping the screen inside the room
+            let rect = room.rect();
+            let half_screen = LEVEL_SIZE.as_vec2() / 2.0;
+            let (min, max) = (rect.min + half_screen, rect.max - half_screen);
+            player.map_or(rect.center(), |p| p.translation.truncate().clamp(min, max))
+        }
+        None => level_selection.0.center(),
+    };
+
     for mut transform in &mut cameras {
-        let target_position = level_selection.0.center().extend(0.0);
+        let target_position = target_position.extend(0.0);
 
         // Exponential smoothing towards target position
         transform.translation = transform.translation.lerp(
@@ -63,10 +63,7 @@ fn change_player_tint(players: Query<&mut Tint, With<Player>>, input: Res<Button
                 warn!("Player should have a color. Use default to GameColor::White");
                 GameColor::White
             }
-            [color, ..] => {
-                warn!("Player should have a single color.");
-                color
-            }
+            [color, ..] => color,
         };
 
         *tint = match color {
@@ -0,0 +1,214 @@
+//! Minimap of the 3x3 grid cells around the player.
+//!
+//! The minimap is an image generated from the `Colortile` layer of the visited
+//! rooms, one pixel per tile, with the uncollected objects on top. The player
+//! is a dot moving over the image.
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    AppSystems,
+    hud::Hud,
+    ldtk::{
+        LdtkProjectData,
+        entities::object::{CollectedObjects, InMuseum},
+        gridvania::{
+            GridCoords, GridLevelSelection, GridvaniaLevels, LEVEL_SIZE, colortile_colors,
+        },
+    },
+    player::Player,
+    save::GameProgress,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MinimapCenter>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_minimap);
+    app.add_systems(
+        Update,
+        (
+            update_minimap_center,
+            draw_minimap.run_if(
+                resource_changed::<MinimapCenter>
+                    .or(resource_changed::<GridLevelSelection>)
+                    .or(resource_changed::<CollectedObjects>)
+                    .or(any_match_filter::<Added<Minimap>>),
+            ),
+            update_player_dot,
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Size of a tile in pixel.
+const TILE_SIZE: i32 = 16;
+
+/// Size of a grid cell in tiles.
+const CELL_TILES: IVec2 = IVec2::new(LEVEL_SIZE.x / TILE_SIZE, LEVEL_SIZE.y / TILE_SIZE);
+
+/// Number of screen pixels per tile.
+const MINIMAP_SCALE: f32 = 2.0;
+
+/// Size of the player dot in pixel.
+const PLAYER_DOT_SIZE: f32 = 4.0;
+
+const OBJECT_COLOR: Color = Color::WHITE;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct Minimap;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PlayerDot;
+
+/// Grid cell of the player, at the center of the minimap.
+#[derive(Resource, Reflect, Debug, Default, PartialEq)]
+#[reflect(Resource)]
+struct MinimapCenter(GridCoords);
+
+fn update_minimap_center(
+    player: Single<&Transform, With<Player>>,
+    mut center: ResMut<MinimapCenter>,
+) {
+    let coords = GridCoords::from_world_position(player.translation.truncate());
+    center.set_if_neq(MinimapCenter(coords));
+}
+
+fn spawn_minimap(mut commands: Commands) {
+    let size = (3 * CELL_TILES).as_vec2() * MINIMAP_SCALE;
+
+    commands.spawn((
+        Name::new("Minimap"),
+        Hud,
+        Minimap,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(8),
+            right: px(8),
+            width: px(size.x),
+            height: px(size.y),
+            border: UiRect::all(px(1)),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        ImageNode::default(),
+        BorderColor::all(ui_palette::LABEL_TEXT),
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        children![(
+            Name::new("Player Dot"),
+            PlayerDot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: px(PLAYER_DOT_SIZE),
+                height: px(PLAYER_DOT_SIZE),
+                ..default()
+            },
+            BackgroundColor(Color::WHITE),
+        )],
+    ));
+}
+
+/// Redraw the minimap image around the cell of the player.
+fn draw_minimap(
+    project: LdtkProjectData,
+    levels: If<Res<GridvaniaLevels>>,
+    center: Res<MinimapCenter>,
+    grid_level_selection: Res<GridLevelSelection>,
+    progress: Res<GameProgress>,
+    collected_objects: Res<CollectedObjects>,
+    mut minimap: Single<&mut ImageNode, With<Minimap>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Some(ldtk_project) = project.get() else {
+        return;
+    };
+    let colors = colortile_colors(ldtk_project);
+
+    let size = (3 * CELL_TILES).as_uvec2();
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let mut put = |position: IVec2, color: Color| {
+        if position.cmpge(IVec2::ZERO).all() && position.cmplt(size.as_ivec2()).all() {
+            let _ = image.set_color_at(position.x as u32, position.y as u32, color);
+        }
+    };
+
+    // Large rooms can cover several of the cells, draw them once
+    let mut rooms = (-1..=1)
+        .flat_map(|y| (-1..=1).map(move |x| ivec2(x, y)))
+        .filter_map(|offset| levels.room_at(center.0.get() + offset))
+        .filter(|room| {
+            room.origin == grid_level_selection.0 || progress.visited_rooms.contains(&room.origin)
+        })
+        .collect::<Vec<_>>();
+    rooms.sort_by_key(|room| (room.origin.get().y, room.origin.get().x));
+    rooms.dedup_by_key(|room| room.origin);
+
+    for room in rooms {
+        let Some(level) = ldtk_project
+            .iter_raw_levels()
+            .find(|level| level.iid == room.iid.as_str())
+        else {
+            continue;
+        };
+
+        // Top left corner of the room in the image, outside pixels are skipped
+        let origin = (room.origin.get() - center.0.get() + IVec2::ONE) * CELL_TILES;
+        for layer in level.layer_instances.iter().flatten() {
+            if layer.identifier == "Colortile" {
+                for (index, value) in layer.int_grid_csv.iter().enumerate() {
+                    let Some(color) = colors.get(value) else {
+                        continue;
+                    };
+                    let tile = ivec2(index as i32 % layer.c_wid, index as i32 / layer.c_wid);
+                    put(origin + tile, color.color());
+                }
+            }
+
+            for instance in &layer.entity_instances {
+                let uncollected = instance.identifier == "Object"
+                    && !*InMuseum::from(instance)
+                    && !collected_objects.contains(&EntityIid::new(instance.iid.clone()));
+                if uncollected {
+                    put(origin + instance.px / TILE_SIZE, OBJECT_COLOR);
+                }
+            }
+        }
+    }
+
+    minimap.image = images.add(image);
+}
+
+fn update_player_dot(
+    player: Single<&Transform, With<Player>>,
+    center: Res<MinimapCenter>,
+    mut dot: Single<&mut Node, With<PlayerDot>>,
+) {
+    // Position of the player from the top left corner of the 3x3 cells
+    let origin = (center.0.get() - IVec2::ONE) * LEVEL_SIZE;
+    let position = player.translation.truncate() * vec2(1.0, -1.0) - origin.as_vec2();
+    let position = position / TILE_SIZE as f32 * MINIMAP_SCALE - PLAYER_DOT_SIZE / 2.0;
+
+    dot.left = px(position.x);
+    dot.top = px(position.y);
+}
@@ -0,0 +1,198 @@
+//! In-game HUD showing the player colors, the collection progress, the
+//! current room, the timed paint and a minimap.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    AppSystems, Pause,
+    ldtk::{
+        LdtkProjectData, Tint,
+        entities::object::{CollectedObjects, InMuseum, ObjectType},
+        gridvania::{GridLevelSelection, GridvaniaLevels},
+    },
+    player::Player,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+mod minimap;
+mod paint_ring;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((minimap::plugin, paint_ring::plugin));
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_hud);
+    app.add_systems(OnEnter(Pause(true)), hide_hud);
+    app.add_systems(OnEnter(Pause(false)), show_hud);
+
+    app.add_systems(
+        Update,
+        (
+            update_color_swatches,
+            update_collection_counts.run_if(
+                resource_changed::<CollectedObjects>.or(any_match_filter::<Added<CollectionCount>>),
+            ),
+            update_room_name.run_if(
+                resource_changed::<GridLevelSelection>.or(any_match_filter::<Added<RoomName>>),
+            ),
+        )
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Size of a color swatch in pixel.
+const SWATCH_SIZE: f32 = 16.0;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct Hud;
+
+/// Container of one swatch per color of the player [`Tint`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct ColorSwatches;
+
+/// Label counting the collected objects of a type.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct CollectionCount(ObjectType);
+
+/// Label showing the name of the current room.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct RoomName;
+
+fn spawn_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("HUD"),
+            Hud,
+            Node {
+                position_type: PositionType::Absolute,
+                top: px(8),
+                left: px(8),
+                flex_direction: FlexDirection::Column,
+                row_gap: px(4),
+                ..default()
+            },
+            Pickable::IGNORE,
+            DespawnOnExit(Screen::Gameplay),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Color Swatches"),
+                ColorSwatches,
+                Node {
+                    column_gap: px(4),
+                    ..default()
+                },
+            ));
+            for object_type in ObjectType::ALL {
+                parent.spawn((widget::label(""), CollectionCount(object_type)));
+            }
+            parent.spawn((widget::label(""), RoomName));
+        });
+}
+
+fn hide_hud(mut huds: Query<&mut Visibility, With<Hud>>) {
+    for mut visibility in &mut huds {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn show_hud(mut huds: Query<&mut Visibility, With<Hud>>) {
+    for mut visibility in &mut huds {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn update_color_swatches(
+    mut commands: Commands,
+    player_tint: Single<Ref<Tint>, With<Player>>,
+    swatches: Single<(Entity, Ref<ColorSwatches>)>,
+) {
+    let (container, swatches) = swatches.into_inner();
+    if !player_tint.is_changed() && !swatches.is_added() {
+        return;
+    }
+
+    let mut colors = player_tint.get_colors();
+    colors.sort_by_key(|color| *color as u8);
+
+    commands
+        .entity(container)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            for color in colors {
+                parent.spawn((
+                    Name::new("Color Swatch"),
+                    Node {
+                        width: px(SWATCH_SIZE),
+                        height: px(SWATCH_SIZE),
+                        border: UiRect::all(px(1)),
+                        ..default()
+                    },
+                    BackgroundColor(color.color()),
+                    BorderColor::all(ui_palette::LABEL_TEXT),
+                ));
+            }
+        });
+}
+
+fn update_collection_counts(
+    project: LdtkProjectData,
+    collected_objects: Res<CollectedObjects>,
+    labels: Query<(&CollectionCount, &mut Text)>,
+) {
+    let Some(ldtk_project) = project.get() else {
+        return;
+    };
+
+    // Collected and total number of objects out of the museum, per type
+    let mut counts = HashMap::<ObjectType, (usize, usize)>::new();
+    for instance in ldtk_project
+        .iter_raw_levels()
+        .flat_map(|level| level.layer_instances.iter().flatten())
+        .flat_map(|layer| &layer.entity_instances)
+        .filter(|instance| instance.identifier == "Object" && !*InMuseum::from(*instance))
+    {
+        let (collected, total) = counts.entry(ObjectType::from(instance)).or_default();
+        *total += 1;
+        if collected_objects.contains(&EntityIid::new(instance.iid.clone())) {
+            *collected += 1;
+        }
+    }
+
+    for (count, mut text) in labels {
+        let (collected, total) = counts.get(&count.0).copied().unwrap_or_default();
+        text.0 = format!("{:?} {collected} / {total}", count.0);
+    }
+}
+
+fn update_room_name(
+    project: LdtkProjectData,
+    grid_level_selection: Res<GridLevelSelection>,
+    levels: If<Res<GridvaniaLevels>>,
+    mut label: Single<&mut Text, With<RoomName>>,
+) {
+    let Some(ldtk_project) = project.get() else {
+        return;
+    };
+    let Some(level_iid) = levels.get_level_at(grid_level_selection.0) else {
+        return;
+    };
+    let Some(level) = ldtk_project
+        .iter_raw_levels()
+        .find(|level| level.iid == level_iid.as_str())
+    else {
+        return;
+    };
+
+    // Unnamed rooms fall back to the LDtk identifier
+    label.0 = match level.get_maybe_string_field("name") {
+        Ok(Some(name)) => name.clone(),
+        _ => level.identifier.clone(),
+    };
+}
@@ -0,0 +1,122 @@
+//! Ring showing the remaining time of the timed paint.
+//!
+//! The ring flashes right before the paint wears off.
+
+use bevy::{
+    prelude::*,
+    render::render_resource::AsBindGroup,
+    shader::ShaderRef,
+    ui_render::prelude::{UiMaterial, UiMaterialPlugin},
+};
+
+use crate::{
+    AppSystems, PausableSystems,
+    hud::Hud,
+    ldtk::{GameColor, Tint},
+    player::{Player, timed_paint::TintHistory},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(UiMaterialPlugin::<PaintRingMaterial>::default());
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_paint_ring);
+    app.add_systems(
+        Update,
+        update_paint_ring
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Remaining time in sec from which the ring flashes.
+const PAINT_WARNING_TIME: f32 = 1.0;
+
+/// Interval between two flashes of the ring in sec.
+const FLASH_INTERVAL: f32 = 0.1;
+
+/// Material drawing the remaining time of the paint as a ring.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+struct PaintRingMaterial {
+    #[uniform(0)]
+    color: LinearRgba,
+    /// Remaining fraction of the paint
+    #[uniform(0)]
+    progress: f32,
+}
+
+impl UiMaterial for PaintRingMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/paint_ring.wgsl".into()
+    }
+}
+
+/// HUD ring showing the remaining time of the timed paint.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct PaintRing;
+
+fn spawn_paint_ring(mut commands: Commands, mut materials: ResMut<Assets<PaintRingMaterial>>) {
+    // The ring is hidden without timed paint, the container with the HUD
+    commands.spawn((
+        Name::new("Paint Ring Container"),
+        Hud,
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(8),
+            right: px(8),
+            ..default()
+        },
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        children![(
+            Name::new("Paint Ring"),
+            PaintRing,
+            Node {
+                width: px(32),
+                height: px(32),
+                ..default()
+            },
+            MaterialNode(materials.add(PaintRingMaterial {
+                color: LinearRgba::WHITE,
+                progress: 0.0,
+            })),
+            Visibility::Hidden,
+        )],
+    ));
+}
+
+fn update_paint_ring(
+    player: Single<(&TintHistory, &Tint), With<Player>>,
+    ring: Single<(&MaterialNode<PaintRingMaterial>, &mut Visibility), With<PaintRing>>,
+    mut materials: ResMut<Assets<PaintRingMaterial>>,
+) {
+    let (history, tint) = player.into_inner();
+    let (material_node, mut visibility) = ring.into_inner();
+
+    let Some(timer) = history.current() else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Inherited;
+
+    let Some(material) = materials.get_mut(&material_node.0) else {
+        return;
+    };
+
+    let remaining = timer.remaining_secs();
+    let flash =
+        remaining < PAINT_WARNING_TIME && ((remaining / FLASH_INTERVAL) as u32).is_multiple_of(2);
+    let color = if flash {
+        GameColor::White.color()
+    } else {
+        tint.get_colors()
+            .first()
+            .map(|color| color.color())
+            .unwrap_or(GameColor::White.color())
+    };
+
+    material.color = color.into();
+    material.progress = timer.fraction_remaining();
+}
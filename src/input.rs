@@ -0,0 +1,231 @@
+//! Player input mapped to game actions.
+//!
+//! Gameplay systems read [`ButtonInput<Action>`] instead of raw keys, so the
+//! keys can be rebound from the settings menu. Gamepads are mapped to the same
+//! actions with a fixed layout.
+
+use bevy::{
+    platform::collections::HashMap,
+    prelude::*,
+    reflect::{DynamicEnum, Enum},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::AppSystems;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InputBindings>();
+    app.init_resource::<ButtonInput<Action>>();
+
+    app.add_systems(Update, record_actions.in_set(AppSystems::RecordInput));
+}
+
+/// Actions the player can perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    SwitchColor,
+    Map,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Self; 6] = [
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Jump,
+        Self::SwitchColor,
+        Self::Map,
+        Self::Pause,
+    ];
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::Jump => "Jump",
+            Action::SwitchColor => "Switch Color",
+            Action::Map => "Map",
+            Action::Pause => "Pause",
+        };
+        f.write_str(text)
+    }
+}
+
+/// Keys bound to each [`Action`].
+///
+/// Keys are stored by name in the settings file. Actions missing from the file
+/// keep their default keys.
+#[derive(Resource, Reflect, Debug, Clone, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(
+    from = "HashMap<Action, Vec<String>>",
+    into = "HashMap<Action, Vec<String>>"
+)]
+pub struct InputBindings(HashMap<Action, Vec<KeyCode>>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self(HashMap::from_iter([
+            (Action::MoveLeft, vec![KeyCode::KeyA, KeyCode::ArrowLeft]),
+            (Action::MoveRight, vec![KeyCode::KeyD, KeyCode::ArrowRight]),
+            (Action::Jump, vec![KeyCode::Space, KeyCode::ArrowUp]),
+            (
+                Action::SwitchColor,
+                vec![KeyCode::KeyE, KeyCode::ShiftRight],
+            ),
+            (Action::Map, vec![KeyCode::KeyM]),
+            (Action::Pause, vec![KeyCode::KeyP, KeyCode::Escape]),
+        ]))
+    }
+}
+
+impl InputBindings {
+    /// Return the keys bound to the action.
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Replace the primary key bound to the action, keeping the other keys.
+    ///
+    /// A key bound to another action is refused and that action is returned,
+    /// so that a key never triggers two actions.
+    pub fn rebind(&mut self, action: Action, key: KeyCode) -> Result<(), Action> {
+        if let Some(other) = Action::ALL
+            .into_iter()
+            .find(|other| *other != action && self.keys(*other).contains(&key))
+        {
+            return Err(other);
+        }
+
+        let keys = self.0.entry(action).or_default();
+        if keys.first() == Some(&key) {
+            return Ok(());
+        }
+
+        keys.retain(|bound| *bound != key);
+        match keys.first_mut() {
+            Some(primary) => *primary = key,
+            None => keys.push(key),
+        }
+        Ok(())
+    }
+}
+
+impl From<HashMap<Action, Vec<String>>> for InputBindings {
+    fn from(names: HashMap<Action, Vec<String>>) -> Self {
+        let mut bindings = Self::default();
+        for (action, names) in names {
+            let keys = names
+                .iter()
+                .filter_map(|name| {
+                    let key = KeyCode::from_reflect(&DynamicEnum::new(name.as_str(), ()));
+                    if key.is_none() {
+                        warn!("Cannot parse {name} as KeyCode.");
+                    }
+                    key
+                })
+                .collect();
+            bindings.0.insert(action, keys);
+        }
+
+        bindings
+    }
+}
+
+impl From<InputBindings> for HashMap<Action, Vec<String>> {
+    fn from(bindings: InputBindings) -> Self {
+        bindings
+            .0
+            .into_iter()
+            .map(|(action, keys)| {
+                let names = keys.iter().map(|key| key_name(*key)).collect();
+                (action, names)
+            })
+            .collect()
+    }
+}
+
+/// Minimal tilt of the left stick to register a horizontal movement.
+const STICK_DEADZONE: f32 = 0.3;
+
+/// Return True if the gamepad performs the action.
+fn gamepad_pressed(gamepad: &Gamepad, action: Action) -> bool {
+    let stick_x = gamepad.left_stick().x;
+    match action {
+        Action::MoveLeft => stick_x < -STICK_DEADZONE || gamepad.pressed(GamepadButton::DPadLeft),
+        Action::MoveRight => stick_x > STICK_DEADZONE || gamepad.pressed(GamepadButton::DPadRight),
+        Action::Jump => gamepad.pressed(GamepadButton::South),
+        Action::SwitchColor => gamepad.pressed(GamepadButton::West),
+        Action::Map => gamepad.pressed(GamepadButton::Select),
+        Action::Pause => gamepad.pressed(GamepadButton::Start),
+    }
+}
+
+/// Return the name of the [`KeyCode`] variant, e.g. `KeyA` or `ArrowLeft`.
+pub fn key_name(key: KeyCode) -> String {
+    key.variant_name().to_owned()
+}
+
+fn record_actions(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    mut actions: ResMut<ButtonInput<Action>>,
+) {
+    actions.clear();
+
+    for action in Action::ALL {
+        if keyboard.any_pressed(bindings.keys(action).iter().copied())
+            || gamepads
+                .iter()
+                .any(|gamepad| gamepad_pressed(gamepad, action))
+        {
+            actions.press(action);
+        } else {
+            actions.release(action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebind_keeps_other_keys() {
+        let mut bindings = InputBindings::default();
+
+        assert!(bindings.rebind(Action::MoveLeft, KeyCode::KeyQ).is_ok());
+        assert_eq!(
+            bindings.keys(Action::MoveLeft),
+            [KeyCode::KeyQ, KeyCode::ArrowLeft]
+        );
+
+        // A secondary key becomes the primary one, without duplicate
+        assert!(
+            bindings
+                .rebind(Action::MoveLeft, KeyCode::ArrowLeft)
+                .is_ok()
+        );
+        assert_eq!(bindings.keys(Action::MoveLeft), [KeyCode::ArrowLeft]);
+    }
+
+    #[test]
+    fn test_rebind_refuses_key_of_other_action() {
+        let mut bindings = InputBindings::default();
+
+        assert_eq!(
+            bindings.rebind(Action::Jump, KeyCode::KeyA),
+            Err(Action::MoveLeft)
+        );
+        assert_eq!(
+            bindings.keys(Action::Jump),
+            [KeyCode::Space, KeyCode::ArrowUp]
+        );
+    }
+}
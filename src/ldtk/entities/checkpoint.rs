@@ -0,0 +1,79 @@
+use avian2d::prelude::{
+    Collider, CollisionEventsEnabled, CollisionLayers, CollisionStart, RigidBody, Sensor,
+};
+use bevy::prelude::*;
+
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    GameLayer,
+    ldtk::Tint,
+    player::{Player, respawn::RespawnPoint},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<CheckpointBundle>("Checkpoint");
+
+    app.add_systems(Update, register_checkpoint_observer);
+}
+
+/// A place where the player respawns after dying, once reached.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Checkpoint;
+
+#[derive(Bundle, LdtkEntity)]
+struct CheckpointBundle {
+    #[default]
+    checkpoint: Checkpoint,
+    #[default]
+    checkpoint_sensor: CheckpointSensorBundle,
+}
+
+#[derive(Bundle)]
+struct CheckpointSensorBundle {
+    body: RigidBody,
+    collider: Collider,
+    sensor: Sensor,
+    collision_event: CollisionEventsEnabled,
+    collision_layer: CollisionLayers,
+}
+
+impl Default for CheckpointSensorBundle {
+    fn default() -> Self {
+        Self {
+            body: RigidBody::Static,
+            collider: Collider::rectangle(16.0, 32.0),
+            sensor: Sensor,
+            collision_event: CollisionEventsEnabled,
+            collision_layer: CollisionLayers::new(GameLayer::Sensor, [GameLayer::Player]),
+        }
+    }
+}
+
+fn register_checkpoint_observer(
+    mut commands: Commands,
+    checkpoints: Query<Entity, Added<Checkpoint>>,
+) {
+    for checkpoint in checkpoints {
+        commands
+            .entity(checkpoint)
+            .observe(detect_checkpoint_reached);
+    }
+}
+
+fn detect_checkpoint_reached(
+    trigger: On<CollisionStart>,
+    checkpoints: Query<&GlobalTransform, With<Checkpoint>>,
+    player_tint: Single<&Tint, With<Player>>,
+    mut respawn_point: ResMut<RespawnPoint>,
+) {
+    let Ok(transform) = checkpoints.get(trigger.event_target()) else {
+        return;
+    };
+
+    *respawn_point = RespawnPoint {
+        position: transform.translation().truncate(),
+        tint: (*player_tint).clone(),
+    };
+}
@@ -0,0 +1,138 @@
+use avian2d::prelude::{
+    Collider, CollisionEnd, CollisionEventsEnabled, CollisionLayers, CollisionStart, RigidBody,
+    Sensor,
+};
+use bevy::prelude::*;
+
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    GameLayer,
+    asset_collection::AudioAssets,
+    audio::{AudioSettings, sound_effect},
+    ldtk::Tint,
+    player::Player,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<ColorSwitchBundle>("ColorSwitch");
+
+    app.add_systems(Update, register_switch_observer);
+}
+
+/// Minimal time between two activations of a switch in sec.
+const SWITCH_COOLDOWN: f32 = 0.5;
+
+/// A switch activated when touched by the player with a matching [`Tint`].
+///
+/// The switch stays pressed until the player leaves it, and cannot be
+/// activated again during [`SWITCH_COOLDOWN`].
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct ColorSwitch {
+    pressed: bool,
+    /// Elapsed time of the last activation in sec.
+    last_activation: Option<f32>,
+}
+
+/// Event triggered when a [`ColorSwitch`] is activated.
+#[derive(Event, Debug)]
+pub struct SwitchActivated {
+    /// LDtk iid of the switch.
+    pub iid: String,
+}
+
+#[derive(Bundle, LdtkEntity)]
+struct ColorSwitchBundle {
+    #[default]
+    color_switch: ColorSwitch,
+    #[with(Tint::from_color_field)]
+    tint: Tint,
+    #[with(switch_sprite)]
+    sprite: Sprite,
+    #[default]
+    switch_sensor: ColorSwitchSensorBundle,
+}
+
+/// A square filled with the color of the switch.
+fn switch_sprite(instance: &EntityInstance) -> Sprite {
+    let color = Tint::from_color_field(instance)
+        .get_colors()
+        .first()
+        .map(|color| color.color())
+        .unwrap_or(Color::WHITE);
+
+    Sprite::from_color(color, Vec2::new(12.0, 12.0))
+}
+
+#[derive(Bundle)]
+struct ColorSwitchSensorBundle {
+    body: RigidBody,
+    collider: Collider,
+    sensor: Sensor,
+    collision_event: CollisionEventsEnabled,
+    collision_layer: CollisionLayers,
+}
+
+impl Default for ColorSwitchSensorBundle {
+    fn default() -> Self {
+        Self {
+            body: RigidBody::Static,
+            collider: Collider::rectangle(16.0, 16.0),
+            sensor: Sensor,
+            collision_event: CollisionEventsEnabled,
+            collision_layer: CollisionLayers::new(GameLayer::Sensor, [GameLayer::Player]),
+        }
+    }
+}
+
+fn register_switch_observer(mut commands: Commands, switches: Query<Entity, Added<ColorSwitch>>) {
+    for switch in switches {
+        commands
+            .entity(switch)
+            .observe(detect_switch_activation)
+            .observe(release_switch);
+    }
+}
+
+fn detect_switch_activation(
+    trigger: On<CollisionStart>,
+    time: Res<Time>,
+    mut commands: Commands,
+    mut switches: Query<(&mut ColorSwitch, &Tint, &EntityIid)>,
+    player_tint: Single<&Tint, With<Player>>,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+) {
+    let Ok((mut switch, switch_tint, iid)) = switches.get_mut(trigger.event_target()) else {
+        return;
+    };
+
+    if switch.pressed || !switch_tint.share_color_with(&player_tint) {
+        return;
+    }
+    let now = time.elapsed_secs();
+    if switch
+        .last_activation
+        .is_some_and(|last| now - last < SWITCH_COOLDOWN)
+    {
+        return;
+    }
+
+    switch.pressed = true;
+    switch.last_activation = Some(now);
+
+    commands.trigger(SwitchActivated {
+        iid: iid.as_str().to_owned(),
+    });
+    commands.spawn(sound_effect(
+        audio_assets.click_sound.clone(),
+        &audio_settings,
+    ));
+}
+
+fn release_switch(trigger: On<CollisionEnd>, mut switches: Query<&mut ColorSwitch>) {
+    if let Ok(mut switch) = switches.get_mut(trigger.event_target()) {
+        switch.pressed = false;
+    }
+}
@@ -0,0 +1,199 @@
+use avian2d::{
+    math::{AdjustPrecision, Vector},
+    prelude::{
+        Collider, ColliderDisabled, CollisionLayers, RigidBody, SpatialQuery, SpatialQueryFilter,
+    },
+};
+use bevy::{prelude::*, sprite::Anchor};
+
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    GameLayer, PausableSystems,
+    ldtk::{Tint, entities::color_switch::SwitchActivated, wall::Wall},
+    save::GameProgress,
+    theme::palette::DOOR,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<DoorBundle>("Door");
+
+    app.add_observer(toggle_doors);
+
+    app.add_systems(
+        Update,
+        (setup_door, animate_door.in_set(PausableSystems)).chain(),
+    );
+}
+
+/// Speed of the door animation in opening fraction per sec.
+const DOOR_SPEED: f32 = 3.0;
+
+/// A door toggled by the [`ColorSwitch`](super::color_switch::ColorSwitch) it
+/// is linked to.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Door {
+    /// LDtk iid of the switches toggling the door.
+    activated_by: Vec<String>,
+    /// Target state of the door.
+    pub open: bool,
+    /// Opening fraction of the door, animated toward `open`.
+    openness: f32,
+    size: Vec2,
+}
+
+impl From<&EntityInstance> for Door {
+    fn from(instance: &EntityInstance) -> Self {
+        let activated_by = instance
+            .get_maybe_entity_refs_field("activated_by")
+            .unwrap()
+            .iter()
+            .flatten()
+            .map(|entity_ref| entity_ref.entity_iid.clone())
+            .collect();
+        let open = *instance.get_bool_field("open").unwrap();
+
+        Self {
+            activated_by,
+            open,
+            openness: if open { 1.0 } else { 0.0 },
+            size: Vec2::new(instance.width as f32, instance.height as f32),
+        }
+    }
+}
+
+/// Visible part of a [`Door`], sliding up when the door opens.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct DoorPanel;
+
+#[derive(Bundle, LdtkEntity)]
+struct DoorBundle {
+    #[from_entity_instance]
+    door: Door,
+    #[from_entity_instance]
+    door_collider: DoorColliderBundle,
+}
+
+/// A closed door is a [`Wall`] without color, solid for every player tint.
+#[derive(Bundle)]
+struct DoorColliderBundle {
+    wall: Wall,
+    tint: Tint,
+    body: RigidBody,
+    collider: Collider,
+    collision_layers: CollisionLayers,
+}
+
+impl From<&EntityInstance> for DoorColliderBundle {
+    fn from(instance: &EntityInstance) -> Self {
+        Self {
+            wall: Wall,
+            tint: Tint::from(None),
+            body: RigidBody::Static,
+            collider: Collider::rectangle(instance.width as f32, instance.height as f32),
+            collision_layers: CollisionLayers::new(GameLayer::Ground, [GameLayer::Player]),
+        }
+    }
+}
+
+/// Restore the saved state of new doors and spawn their panel.
+fn setup_door(
+    mut commands: Commands,
+    doors: Query<(Entity, &mut Door, &EntityIid), Added<Door>>,
+    progress: Res<GameProgress>,
+) {
+    for (entity, mut door, iid) in doors {
+        if let Some(open) = progress.door_states.get(iid.as_str()) {
+            door.open = *open;
+            door.openness = if *open { 1.0 } else { 0.0 };
+        }
+
+        if door.open {
+            commands.entity(entity).insert(ColliderDisabled);
+        }
+
+        commands.entity(entity).with_child((
+            Name::new("Door Panel"),
+            DoorPanel,
+            Sprite::from_color(DOOR, door.size * vec2(1.0, 1.0 - door.openness)),
+            Anchor::TOP_CENTER,
+            Transform::from_xyz(0.0, door.size.y / 2.0, 0.0),
+        ));
+    }
+}
+
+fn toggle_doors(
+    trigger: On<SwitchActivated>,
+    doors: Query<(&mut Door, &EntityIid)>,
+    mut progress: ResMut<GameProgress>,
+) {
+    for (mut door, iid) in doors {
+        if !door.activated_by.contains(&trigger.iid) {
+            continue;
+        }
+
+        door.open = !door.open;
+        progress
+            .door_states
+            .insert(iid.as_str().to_owned(), door.open);
+    }
+}
+
+fn animate_door(
+    time: Res<Time>,
+    mut commands: Commands,
+    doors: Query<(
+        Entity,
+        &mut Door,
+        &Children,
+        &Collider,
+        &GlobalTransform,
+        Has<ColliderDisabled>,
+    )>,
+    mut panels: Query<&mut Sprite, With<DoorPanel>>,
+    spatial_query: SpatialQuery,
+) {
+    for (entity, mut door, children, collider, transform, disabled) in doors {
+        let target = if door.open { 1.0 } else { 0.0 };
+        if door.openness == target {
+            continue;
+        }
+
+        // The door becomes solid as soon as it starts closing, once the player
+        // is out of the way
+        if !door.open && disabled {
+            let position = transform.translation().xy().adjust_precision();
+            if overlaps_player(&spatial_query, collider, position) {
+                continue;
+            }
+            commands.entity(entity).remove::<ColliderDisabled>();
+        }
+
+        let step = DOOR_SPEED * time.delta_secs();
+        door.openness = if door.open {
+            (door.openness + step).min(target)
+        } else {
+            (door.openness - step).max(target)
+        };
+
+        // The door lets the player through once fully open
+        if door.openness == 1.0 {
+            commands.entity(entity).insert(ColliderDisabled);
+        }
+
+        let mut panels = panels.iter_many_mut(children);
+        while let Some(mut sprite) = panels.fetch_next() {
+            sprite.custom_size = Some(door.size * vec2(1.0, 1.0 - door.openness));
+        }
+    }
+}
+
+/// Return True if the player stands in the door.
+fn overlaps_player(spatial_query: &SpatialQuery, collider: &Collider, position: Vector) -> bool {
+    let filter = SpatialQueryFilter::from_mask(GameLayer::Player);
+    !spatial_query
+        .shape_intersections(collider, position, 0.0, &filter)
+        .is_empty()
+}
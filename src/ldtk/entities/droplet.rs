@@ -1,22 +1,37 @@
-use avian2d::prelude::{
-    Collider, CollisionEventsEnabled, CollisionLayers, CollisionStart, RigidBody, Sensor,
+use avian2d::{
+    math::AdjustPrecision,
+    prelude::{
+        Collider, CollisionEventsEnabled, CollisionLayers, CollisionStart, RigidBody, Sensor,
+        SpatialQuery,
+    },
 };
 use bevy::prelude::*;
 
 use bevy_ecs_ldtk::prelude::*;
 
 use crate::{
-    GameLayer,
+    GameLayer, PausableSystems,
     asset_collection::AudioAssets,
     audio::{AudioSettings, sound_effect},
-    ldtk::{GameColor, Tint, entities::object::ObjectLevitation},
-    player::Player,
+    ldtk::{GameColor, Tint, entities::object::ObjectLevitation, wall::Wall},
+    player::{
+        Player, embed::TintRejected, palette::ColorPalette, physics::overlaps_solid_wall,
+        timed_paint::TintHistory,
+    },
+    save::GameProgress,
+    screens::Screen,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_ldtk_entity::<DropletBundle>("Droplet");
 
     app.add_systems(Update, register_droplet_observer);
+    app.add_systems(
+        Update,
+        restore_droplet_supply
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
 }
 
 #[derive(Component, Reflect, Debug)]
@@ -35,12 +50,115 @@ impl From<&EntityInstance> for Droplet {
     }
 }
 
+/// The droplet unlocks [`ColorMixing`] when picked up.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct UnlockMixing(pub bool);
+
+impl From<&EntityInstance> for UnlockMixing {
+    fn from(instance: &EntityInstance) -> Self {
+        let mixing = instance.get_bool_field("mixing").unwrap().to_owned();
+        Self(mixing)
+    }
+}
+
+/// Pickups available from a droplet.
+///
+/// A consumable droplet runs out after `charges` pickups. It then fades out and
+/// reappears with full charges after `respawn_time`.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct DropletSupply {
+    pub consumable: bool,
+    pub charges: u32,
+    remaining: u32,
+    respawn: Timer,
+}
+
+impl DropletSupply {
+    /// Return True if the droplet can be picked up.
+    pub fn is_available(&self) -> bool {
+        self.remaining > 0
+    }
+
+    /// Use one charge of a consumable droplet.
+    fn consume(&mut self) {
+        if !self.consumable {
+            return;
+        }
+
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining == 0 {
+            self.respawn.reset();
+        }
+    }
+}
+
+impl From<&EntityInstance> for DropletSupply {
+    fn from(instance: &EntityInstance) -> Self {
+        let consumable = *instance.get_bool_field("consumable").unwrap();
+        let respawn_time = *instance.get_float_field("respawn_time").unwrap();
+        let charges = (*instance.get_int_field("charges").unwrap()).max(1) as u32;
+
+        Self {
+            consumable,
+            charges,
+            remaining: charges,
+            respawn: Timer::from_seconds(respawn_time.max(0.0), TimerMode::Once),
+        }
+    }
+}
+
+/// Duration of the fade out of a consumed droplet in sec.
+const DROPLET_FADE_DURATION: f32 = 0.25;
+
+/// Alpha of a consumed droplet once faded out, it grows back up to
+/// [`DROPLET_COOLDOWN_ALPHA`] while waiting to reappear.
+const DROPLET_FADED_ALPHA: f32 = 0.1;
+const DROPLET_COOLDOWN_ALPHA: f32 = 0.4;
+
+/// Time in sec before the color of the droplet wears off, `None` if permanent.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct PaintDuration(pub Option<f32>);
+
+impl From<&EntityInstance> for PaintDuration {
+    fn from(instance: &EntityInstance) -> Self {
+        let duration = instance
+            .get_maybe_float_field("paint_duration")
+            .unwrap()
+            .to_owned();
+        Self(duration)
+    }
+}
+
+/// Late-game ability: droplets add their color to the player [`Tint`]
+/// instead of replacing it.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ColorMixing {
+    /// Maximal number of colors held at once
+    pub max_colors: usize,
+}
+
+impl Default for ColorMixing {
+    fn default() -> Self {
+        Self { max_colors: 2 }
+    }
+}
+
 #[derive(Bundle, LdtkEntity)]
 struct DropletBundle {
     #[sprite_sheet]
     sprite: Sprite,
     #[from_entity_instance]
     droplet: Droplet,
+    #[from_entity_instance]
+    unlock_mixing: UnlockMixing,
+    #[from_entity_instance]
+    supply: DropletSupply,
+    #[from_entity_instance]
+    paint_duration: PaintDuration,
     #[default]
     levitation: ObjectLevitation,
     #[default]
@@ -77,18 +195,107 @@ fn register_droplet_observer(mut commands: Commands, droplets: Query<Entity, Add
 fn detect_droplet_pickup(
     trigger: On<CollisionStart>,
     mut commands: Commands,
-    droplets: Query<&Droplet>,
-    mut player_tint: Single<&mut Tint, With<Player>>,
+    mut droplets: Query<(&Droplet, &UnlockMixing, &PaintDuration, &mut DropletSupply)>,
+    player: Single<
+        (
+            Entity,
+            &mut Tint,
+            &mut TintHistory,
+            &mut ColorPalette,
+            Option<&ColorMixing>,
+            &Transform,
+            &Collider,
+        ),
+        With<Player>,
+    >,
+    walls: Query<&Tint, (With<Wall>, Without<Player>)>,
+    spatial_query: SpatialQuery,
+    mut progress: ResMut<GameProgress>,
     audio_assets: Res<AudioAssets>,
     audio_settings: Res<AudioSettings>,
 ) {
-    let Ok(droplet) = droplets.get(trigger.event_target()) else {
+    let Ok((droplet, unlock_mixing, paint_duration, mut supply)) =
+        droplets.get_mut(trigger.event_target())
+    else {
         return;
     };
+    if !supply.is_available() {
+        return;
+    }
+
+    let (player, mut tint, mut history, mut palette, mixing, transform, collider) =
+        player.into_inner();
+
+    let mut new_tint = tint.clone();
+    match mixing {
+        Some(mixing) => new_tint.mix(droplet.0, mixing.max_colors),
+        None => new_tint = Tint::from_color(droplet.0),
+    }
 
-    **player_tint = Tint::from_color(droplet.0);
+    // A pickup embedding the player in a wall is rejected before spending anything
+    let position = transform.translation.xy().adjust_precision();
+    if new_tint != *tint
+        && overlaps_solid_wall(&spatial_query, collider, position, &new_tint, &walls)
+    {
+        commands.trigger(TintRejected);
+        return;
+    }
+
+    // Timed paint does not unlock its color
+    if paint_duration.0.is_none() {
+        palette.unlock(droplet.0);
+    }
+
+    if unlock_mixing.0 && mixing.is_none() {
+        commands.entity(player).insert(ColorMixing::default());
+        progress.color_mixing = true;
+    }
+
+    if new_tint == *tint {
+        // Touching again a timed droplet of the paint being worn restarts it,
+        // other droplets without effect neither use a charge nor play a sound
+        let refreshed = paint_duration
+            .0
+            .is_some_and(|duration| history.refresh(droplet.0, duration));
+        if !refreshed {
+            return;
+        }
+    } else {
+        match paint_duration.0 {
+            Some(duration) => history.push(tint.clone(), droplet.0, duration),
+            None => history.clear(),
+        }
+        *tint = new_tint;
+    }
+    supply.consume();
     commands.spawn(sound_effect(
         audio_assets.paint_spray.clone(),
         &audio_settings,
     ));
 }
+
+/// Fade out consumed droplets and make them reappear after their respawn time.
+fn restore_droplet_supply(
+    time: Res<Time>,
+    droplets: Query<(&mut DropletSupply, &mut Sprite), With<Droplet>>,
+) {
+    for (mut supply, mut sprite) in droplets {
+        if supply.is_available() {
+            continue;
+        }
+
+        supply.respawn.tick(time.delta());
+
+        let alpha = if supply.respawn.is_finished() {
+            supply.remaining = supply.charges;
+            1.0
+        } else if supply.respawn.elapsed_secs() < DROPLET_FADE_DURATION {
+            let fade = supply.respawn.elapsed_secs() / DROPLET_FADE_DURATION;
+            1.0 - fade * (1.0 - DROPLET_FADED_ALPHA)
+        } else {
+            let cooldown = supply.respawn.fraction();
+            DROPLET_FADED_ALPHA + cooldown * (DROPLET_COOLDOWN_ALPHA - DROPLET_FADED_ALPHA)
+        };
+        sprite.color.set_alpha(alpha);
+    }
+}
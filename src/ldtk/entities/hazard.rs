@@ -0,0 +1,105 @@
+use avian2d::prelude::{
+    Collider, CollisionEventsEnabled, CollisionLayers, CollisionStart, RigidBody, Sensor,
+};
+use bevy::prelude::*;
+
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    GameLayer,
+    ldtk::Tint,
+    player::{Player, respawn::PlayerDied},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_ldtk_entity::<HazardBundle>("Hazard");
+    app.register_ldtk_int_cell_for_layer::<HazardCellBundle>("Hazards", 1);
+
+    app.add_systems(Update, register_hazard_observer);
+}
+
+/// An area killing the player on contact (spikes, paint pools...).
+///
+/// The player is immune if it shares a color with the hazard.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Hazard;
+
+#[derive(Bundle, LdtkEntity)]
+struct HazardBundle {
+    #[default]
+    hazard: Hazard,
+    #[with(Tint::from_colors_field)]
+    tint: Tint,
+    #[from_entity_instance]
+    hazard_sensor: HazardSensorBundle,
+}
+
+/// A hazard tile of the Hazards layer, deadly whatever the player color.
+#[derive(Bundle, LdtkIntCell)]
+struct HazardCellBundle {
+    #[default]
+    hazard: Hazard,
+    #[with(colorless)]
+    tint: Tint,
+    #[from_int_grid_cell]
+    hazard_sensor: HazardSensorBundle,
+}
+
+fn colorless(_: IntGridCell) -> Tint {
+    Tint::from(None)
+}
+
+#[derive(Bundle)]
+struct HazardSensorBundle {
+    body: RigidBody,
+    collider: Collider,
+    sensor: Sensor,
+    collision_event: CollisionEventsEnabled,
+    collision_layer: CollisionLayers,
+}
+
+impl HazardSensorBundle {
+    fn new(width: f32, height: f32) -> Self {
+        Self {
+            body: RigidBody::Static,
+            collider: Collider::rectangle(width, height),
+            sensor: Sensor,
+            collision_event: CollisionEventsEnabled,
+            collision_layer: CollisionLayers::new(GameLayer::Sensor, [GameLayer::Player]),
+        }
+    }
+}
+
+impl From<&EntityInstance> for HazardSensorBundle {
+    fn from(instance: &EntityInstance) -> Self {
+        Self::new(instance.width as f32, instance.height as f32)
+    }
+}
+
+impl From<IntGridCell> for HazardSensorBundle {
+    fn from(_: IntGridCell) -> Self {
+        Self::new(16.0, 16.0)
+    }
+}
+
+fn register_hazard_observer(mut commands: Commands, hazards: Query<Entity, Added<Hazard>>) {
+    for hazard in hazards {
+        commands.entity(hazard).observe(detect_hazard_contact);
+    }
+}
+
+fn detect_hazard_contact(
+    trigger: On<CollisionStart>,
+    mut commands: Commands,
+    hazards: Query<&Tint, With<Hazard>>,
+    player_tint: Single<&Tint, With<Player>>,
+) {
+    let Ok(hazard_tint) = hazards.get(trigger.event_target()) else {
+        return;
+    };
+
+    if !hazard_tint.share_color_with(&player_tint) {
+        commands.trigger(PlayerDied);
+    }
+}
@@ -2,9 +2,22 @@
 
 use bevy::prelude::*;
 
+pub mod checkpoint;
+pub mod color_switch;
+pub mod door;
 pub mod droplet;
+pub mod hazard;
+pub mod museum;
 pub mod object;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((object::plugin, droplet::plugin));
+    app.add_plugins((
+        checkpoint::plugin,
+        color_switch::plugin,
+        door::plugin,
+        droplet::plugin,
+        hazard::plugin,
+        museum::plugin,
+        object::plugin,
+    ));
 }
@@ -0,0 +1,176 @@
+//! The museum, progression hub of the game.
+//!
+//! Collected objects are carried in the [`Inventory`]. Touching a museum copy of
+//! the same [`ObjectType`] places one on its pedestal, turning the copy from a
+//! silhouette to full color.
+
+use avian2d::prelude::CollisionStart;
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::{
+    asset_collection::AudioAssets,
+    audio::{AudioSettings, sound_effect},
+    ldtk::{
+        LdtkProjectData,
+        entities::object::{InMuseum, ObjectType},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Inventory>();
+    app.init_resource::<Museum>();
+
+    app.add_systems(
+        Update,
+        (
+            register_pedestals,
+            register_pedestal_observer,
+            update_pedestal_appearance,
+        ),
+    );
+}
+
+/// Color of the museum copies not displayed yet.
+const SILHOUETTE_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+/// Objects collected and carried by the player, not yet displayed in the museum.
+#[derive(Resource, Reflect, Debug, Default, Deref, DerefMut)]
+#[reflect(Resource)]
+pub struct Inventory(pub Vec<ObjectType>);
+
+impl Inventory {
+    /// Remove an object of the given type, return False if none is carried.
+    fn take(&mut self, object_type: ObjectType) -> bool {
+        match self.0.iter().position(|carried| *carried == object_type) {
+            Some(index) => {
+                self.0.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Pedestals of the museum and the objects displayed on them.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct Museum {
+    /// Type of the object expected by each pedestal, by LDtk iid.
+    pedestals: HashMap<EntityIid, ObjectType>,
+    /// Pedestals displaying their object.
+    pub displayed: HashSet<EntityIid>,
+}
+
+impl Museum {
+    /// Return the number of displayed objects and the number of pedestals.
+    pub fn completion(&self) -> (usize, usize) {
+        (self.displayed.len(), self.pedestals.len())
+    }
+
+    /// Return the number of displayed objects and the number of pedestals of
+    /// the given type.
+    pub fn completion_of(&self, object_type: ObjectType) -> (usize, usize) {
+        let pedestals = self
+            .pedestals
+            .iter()
+            .filter(|(_, pedestal_type)| **pedestal_type == object_type)
+            .map(|(iid, _)| iid)
+            .collect::<Vec<_>>();
+
+        let displayed = pedestals
+            .iter()
+            .filter(|iid| self.displayed.contains(**iid))
+            .count();
+        (displayed, pedestals.len())
+    }
+}
+
+/// List the pedestals of the whole world, including the levels not spawned.
+fn register_pedestals(
+    mut museum: ResMut<Museum>,
+    mut registered: Local<bool>,
+    project: LdtkProjectData,
+) {
+    if *registered {
+        return;
+    }
+    let Some(ldtk_project) = project.get() else {
+        return;
+    };
+
+    museum.pedestals = ldtk_project
+        .json_data()
+        .levels
+        .iter()
+        .flat_map(|level| level.layer_instances.iter().flatten())
+        .flat_map(|layer| &layer.entity_instances)
+        .filter(|instance| instance.identifier == "Object" && *InMuseum::from(*instance))
+        .map(|instance| {
+            (
+                EntityIid::new(instance.iid.clone()),
+                ObjectType::from(instance),
+            )
+        })
+        .collect();
+    *registered = true;
+}
+
+fn register_pedestal_observer(
+    mut commands: Commands,
+    objects: Query<(Entity, &InMuseum), Added<InMuseum>>,
+) {
+    for (object, in_museum) in objects {
+        if **in_museum {
+            commands.entity(object).observe(deliver_object);
+        }
+    }
+}
+
+fn deliver_object(
+    trigger: On<CollisionStart>,
+    mut commands: Commands,
+    pedestals: Query<(&ObjectType, &EntityIid)>,
+    mut inventory: ResMut<Inventory>,
+    mut museum: ResMut<Museum>,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+) {
+    let Ok((object_type, iid)) = pedestals.get(trigger.event_target()) else {
+        return;
+    };
+
+    if museum.displayed.contains(iid) || !inventory.take(*object_type) {
+        return;
+    }
+
+    museum.displayed.insert(iid.clone());
+    commands.spawn(sound_effect(
+        audio_assets.paint_spray.clone(),
+        &audio_settings,
+    ));
+}
+
+fn update_pedestal_appearance(
+    objects: Query<(&mut Sprite, &InMuseum, &EntityIid)>,
+    museum: Res<Museum>,
+) {
+    for (mut sprite, in_museum, iid) in objects {
+        if !**in_museum {
+            continue;
+        }
+
+        let color = if museum.displayed.contains(iid) {
+            Color::WHITE
+        } else {
+            SILHOUETTE_COLOR
+        };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
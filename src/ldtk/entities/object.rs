@@ -3,20 +3,36 @@ use std::str::FromStr;
 use avian2d::{
     math::TAU,
     prelude::{
-        Collider, CollisionEventsEnabled, CollisionLayers, CollisionStart, RigidBody, Sensor,
+        Collider, ColliderDisabled, CollisionEventsEnabled, CollisionLayers, CollisionStart,
+        RigidBody,
     },
 };
-use bevy::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
 
 use bevy_ecs_ldtk::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::{GameLayer, ldtk::Tint, player::Player};
+use crate::{
+    GameLayer,
+    ldtk::{Tint, entities::museum::Inventory},
+    player::Player,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_ldtk_entity::<ObjectBundle>("Object");
 
+    app.init_resource::<CollectedObjects>();
+    app.add_observer(skip_collected_object);
+
     app.add_systems(Update, (register_initial_position, levitate_object).chain());
     app.add_systems(Update, register_collision_observer);
+    app.add_systems(
+        Update,
+        respawn_restored_objects.run_if(resource_changed::<CollectedObjects>),
+    );
+    // Hide once the physics step is over, disabling colliders from the
+    // collision observers panics in Avian
+    app.add_systems(PostUpdate, hide_collected_objects);
 }
 
 #[derive(Component, Reflect, Debug)]
@@ -62,7 +78,7 @@ impl From<&EntityInstance> for InMuseum {
     }
 }
 
-#[derive(Component, Reflect, Debug)]
+#[derive(Component, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[reflect(Component)]
 pub enum ObjectType {
     Book,
@@ -71,6 +87,10 @@ pub enum ObjectType {
     Parchment,
 }
 
+impl ObjectType {
+    pub const ALL: [Self; 4] = [Self::Book, Self::Feather, Self::Ink, Self::Parchment];
+}
+
 impl FromStr for ObjectType {
     type Err = String;
 
@@ -92,6 +112,19 @@ impl From<&EntityInstance> for ObjectType {
     }
 }
 
+/// Objects picked up by the player.
+///
+/// Collected objects stay hidden, even when their level is reloaded. Removing
+/// an object from the set shows it again.
+#[derive(Resource, Reflect, Debug, Default, Deref, DerefMut)]
+#[reflect(Resource)]
+pub struct CollectedObjects(pub HashSet<EntityIid>);
+
+/// The object was collected and is hidden at the end of the frame.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct Collected;
+
 #[derive(Bundle, LdtkEntity)]
 struct ObjectBundle {
     #[sprite_sheet]
@@ -155,23 +188,114 @@ fn register_collision_observer(mut commands: Commands, objects: Query<Entity, Ad
 fn detect_object_pickup(
     trigger: On<CollisionStart>,
     mut commands: Commands,
-    mut objects: Query<(&mut Visibility, &Tint, &InMuseum), With<ObjectType>>,
+    objects: Query<(&ObjectType, &Tint, &InMuseum, &EntityIid), Without<Collected>>,
     player_tint: Single<&Tint, With<Player>>,
+    mut collected_objects: ResMut<CollectedObjects>,
+    mut inventory: ResMut<Inventory>,
 ) {
-    let Ok((mut object_visibility, object_tint, in_museum)) =
-        objects.get_mut(trigger.event_target())
-    else {
+    let Ok((object_type, object_tint, in_museum, iid)) = objects.get(trigger.event_target()) else {
         return;
     };
 
-    // Do not pickup museum objects
+    // Museum objects are pedestals, see `museum::deliver_object`
     if **in_museum {
         return;
     }
 
     if object_tint.share_color_with(&player_tint) {
-        // TODO: Change this to despawn. Simple despawn panic with physics engine.
-        *object_visibility = Visibility::Hidden;
-        commands.entity(trigger.event_target()).insert(Sensor);
+        commands.entity(trigger.event_target()).insert(Collected);
+        collected_objects.insert(iid.clone());
+        inventory.push(*object_type);
+    }
+}
+
+/// Skip the objects already collected as soon as `bevy_ecs_ldtk` spawns them.
+///
+/// Triggered for both components, so that it runs once both are inserted. The
+/// entity is still being built, so it is hidden later with the other
+/// [`Collected`] objects.
+fn skip_collected_object(
+    trigger: On<Add, (ObjectType, EntityIid)>,
+    mut commands: Commands,
+    objects: Query<&EntityIid, With<ObjectType>>,
+    collected_objects: Res<CollectedObjects>,
+) {
+    let Ok(iid) = objects.get(trigger.event_target()) else {
+        return;
+    };
+
+    if collected_objects.contains(iid) {
+        commands.entity(trigger.event_target()).insert(Collected);
+    }
+}
+
+/// Show again the objects removed from [`CollectedObjects`].
+fn respawn_restored_objects(
+    mut commands: Commands,
+    collected_objects: Res<CollectedObjects>,
+    objects: Query<(Entity, &EntityIid, &mut Visibility), With<Collected>>,
+) {
+    for (entity, iid, mut visibility) in objects {
+        if !collected_objects.contains(iid) {
+            *visibility = Visibility::Inherited;
+            commands
+                .entity(entity)
+                .remove::<(Collected, ColliderDisabled)>();
+        }
+    }
+}
+
+fn hide_collected_objects(
+    mut commands: Commands,
+    objects: Query<(Entity, &mut Visibility), Added<Collected>>,
+) {
+    for (entity, mut visibility) in objects {
+        *visibility = Visibility::Hidden;
+        commands.entity(entity).insert(ColliderDisabled);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_collected_object() {
+        let mut app = App::new();
+        app.init_resource::<CollectedObjects>()
+            .add_observer(skip_collected_object)
+            .add_systems(
+                Update,
+                respawn_restored_objects.run_if(resource_changed::<CollectedObjects>),
+            )
+            .add_systems(PostUpdate, hide_collected_objects);
+
+        let iid = EntityIid::new("object");
+        app.world_mut()
+            .resource_mut::<CollectedObjects>()
+            .insert(iid.clone());
+        let object = app
+            .world_mut()
+            .spawn((ObjectType::Book, iid.clone(), Visibility::default()))
+            .id();
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Visibility>(object),
+            Some(&Visibility::Hidden)
+        );
+        assert!(app.world().entity(object).contains::<ColliderDisabled>());
+
+        app.world_mut()
+            .resource_mut::<CollectedObjects>()
+            .remove(&iid);
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Visibility>(object),
+            Some(&Visibility::Inherited)
+        );
+        assert!(!app.world().entity(object).contains::<Collected>());
+        assert!(!app.world().entity(object).contains::<ColliderDisabled>());
     }
 }
@@ -1,7 +1,12 @@
 use bevy::{platform::collections::HashMap, prelude::*};
-use bevy_ecs_ldtk::prelude::*;
+use bevy_ecs_ldtk::{ldtk::Level, prelude::*};
 
-use crate::{asset_collection::LevelAssets, player::Player, screens::Screen};
+use crate::{
+    asset_collection::LevelAssets,
+    ldtk::{GameColor, LdtkProjectData},
+    player::{Player, respawn::PlayerDied},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<GridLevelSelection>();
@@ -20,13 +25,16 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+/// Size of a cell of the GridVania world, rooms span one or more cells.
 pub const LEVEL_SIZE: IVec2 = IVec2::new(512, 288);
 
 /// Position of a Level in the GridVania world.
 ///
 /// The level at position (0, 0) is the one with the upper left corner at
 /// (0, 0) in world coords.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default, serde::Serialize, serde::Deserialize,
+)]
 pub struct GridCoords(IVec2);
 
 impl GridCoords {
@@ -45,7 +53,7 @@ impl GridCoords {
         Self(grid_pos)
     }
 
-    /// Return the 2D Bevy position of the center of the Level represented by the coordinates.
+    /// Return the 2D Bevy position of the center of the cell represented by the coordinates.
     pub fn center(&self) -> Vec2 {
         let ldtk_position = self.0 * LEVEL_SIZE + LEVEL_SIZE / 2;
         (ldtk_position * ivec2(1, -1)).as_vec2()
@@ -72,39 +80,155 @@ impl GridLevelSelection {
     }
 }
 
-/// Resource that maps a [`GridCoords`] to a [`LevelIid`].
+/// A level of the GridVania world, spanning one or more cells.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct Room {
+    pub iid: LevelIid,
+    /// Top left cell of the room, identifying the room in the grid.
+    pub origin: GridCoords,
+    /// Size of the room in cells.
+    pub size: IVec2,
+}
+
+impl Room {
+    fn from_level(level: &Level) -> Self {
+        let world_coords = ivec2(level.world_x, -level.world_y).as_vec2();
+        let size = ivec2(level.px_wid, level.px_hei).as_vec2() / LEVEL_SIZE.as_vec2();
+        Self {
+            iid: LevelIid::new(level.iid.clone()),
+            origin: GridCoords::from_world_position(world_coords),
+            size: size.ceil().as_ivec2().max(IVec2::ONE),
+        }
+    }
+
+    /// Return the coordinates of all the cells of the room.
+    pub fn cells(&self) -> impl Iterator<Item = GridCoords> + '_ {
+        (0..self.size.y).flat_map(move |y| {
+            (0..self.size.x).map(move |x| GridCoords(self.origin.0 + ivec2(x, y)))
+        })
+    }
+
+    /// Return the 2D Bevy area covered by the room.
+    pub fn rect(&self) -> Rect {
+        let min = self.origin.0 * LEVEL_SIZE;
+        let max = min + self.size * LEVEL_SIZE;
+        Rect::new(min.x as f32, -min.y as f32, max.x as f32, -max.y as f32)
+    }
+}
+
+/// Resource that maps each [`GridCoords`] to the [`Room`] covering it.
 ///
 /// Automatically filled and added when the [`LdtkProject`] is loaded.
 #[derive(Resource, Reflect, Debug, Default)]
 #[reflect(Resource)]
-pub struct GridvaniaLevels(HashMap<GridCoords, LevelIid>);
+pub struct GridvaniaLevels(HashMap<GridCoords, Room>);
 
 impl GridvaniaLevels {
+    /// Map the cells of every level to their room.
+    fn from_levels<'a>(levels: impl IntoIterator<Item = &'a Level>) -> Self {
+        let level_map = levels
+            .into_iter()
+            .map(Room::from_level)
+            .flat_map(|room| {
+                room.cells()
+                    .map(|coords| (coords, room.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Self(level_map)
+    }
+
     pub fn get_level_at(&self, coords: impl Into<GridCoords>) -> Option<LevelIid> {
-        self.0.get(&coords.into()).cloned()
+        self.room_at(coords).map(|room| room.iid.clone())
+    }
+
+    /// Return the room covering the cell at the coordinates.
+    pub fn room_at(&self, coords: impl Into<GridCoords>) -> Option<&Room> {
+        self.0.get(&coords.into())
+    }
+
+    /// Return the coordinates of all the cells covered by a room.
+    pub fn coords(&self) -> impl Iterator<Item = GridCoords> + '_ {
+        self.0.keys().copied()
+    }
+
+    /// Return every room once.
+    pub fn rooms(&self) -> impl Iterator<Item = &Room> {
+        self.0
+            .iter()
+            .filter(|(coords, room)| **coords == room.origin)
+            .map(|(_, room)| room)
     }
 }
 
-fn populate_gridvania_levels(
-    mut commands: Commands,
-    level_assets: Res<LevelAssets>,
-    ldtk_project_assets: Res<Assets<LdtkProject>>,
-) {
-    let ldtk_project = ldtk_project_assets
-        .get(level_assets.world.id())
-        .expect("Project should be loaded by then.");
+/// Resource that maps the origin of a [`Room`] to the most used color of the
+/// `Colortile` layer of the level.
+///
+/// Automatically filled and added when the [`LdtkProject`] is loaded.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct RoomColors(HashMap<GridCoords, GameColor>);
 
-    let level_map = ldtk_project
+impl RoomColors {
+    pub fn get(&self, coords: impl Into<GridCoords>) -> Option<GameColor> {
+        self.0.get(&coords.into()).copied()
+    }
+}
+
+/// Return the [`GameColor`] of each value of the `Colortile` IntGrid layer.
+pub fn colortile_colors(ldtk_project: &LdtkProject) -> HashMap<i32, GameColor> {
+    ldtk_project
+        .json_data()
+        .defs
+        .layers
+        .iter()
+        .filter(|layer| layer.identifier == "Colortile")
+        .flat_map(|layer| &layer.int_grid_values)
+        .filter_map(|value| {
+            let color = value.identifier.as_ref()?.parse::<GameColor>().ok()?;
+            Some((value.value, color))
+        })
+        .collect()
+}
+
+/// Return the most used color of the `Colortile` layer of the level.
+fn dominant_color(level: &Level, colors: &HashMap<i32, GameColor>) -> Option<GameColor> {
+    let mut counts = HashMap::<GameColor, usize>::new();
+    for value in level
+        .layer_instances
+        .iter()
+        .flatten()
+        .filter(|layer| layer.identifier == "Colortile")
+        .flat_map(|layer| &layer.int_grid_csv)
+    {
+        if let Some(color) = colors.get(value) {
+            *counts.entry(*color).or_default() += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|(color, count)| (*count, *color as u8))
+        .map(|(color, _)| color)
+}
+
+fn populate_gridvania_levels(mut commands: Commands, project: LdtkProjectData) {
+    let ldtk_project = project.get().expect("Project should be loaded by then.");
+
+    let levels = GridvaniaLevels::from_levels(ldtk_project.iter_raw_levels());
+
+    let colors = colortile_colors(ldtk_project);
+    let room_colors = ldtk_project
         .iter_raw_levels()
-        .map(|l| {
-            let world_coords = ivec2(l.world_x, -l.world_y).as_vec2();
-            let grid_coords = GridCoords::from_world_position(world_coords);
-            (grid_coords, LevelIid::new(l.iid.clone()))
+        .filter_map(|l| {
+            let origin = Room::from_level(l).origin;
+            dominant_color(l, &colors).map(|color| (origin, color))
         })
         .collect::<HashMap<_, _>>();
 
-    info!("{} level loaded.", level_map.len());
-    commands.insert_resource(GridvaniaLevels(level_map));
+    info!("{} level loaded.", levels.rooms().count());
+    commands.insert_resource(levels);
+    commands.insert_resource(RoomColors(room_colors));
 }
 
 fn update_level_selection(
@@ -119,21 +243,196 @@ fn update_level_selection(
     }
 }
 
+/// Select the room under the player, by its origin.
+///
+/// Cells without a level act as a kill zone: the player dies and the selection
+/// stays on the last existing level, so the camera and LDtk stay in sync.
 fn level_selection_follow_player(
+    mut commands: Commands,
     player: Single<&Transform, With<Player>>,
     mut grid_level_selection: ResMut<GridLevelSelection>,
+    levels: If<Res<GridvaniaLevels>>,
 ) {
     let grid_pos = GridCoords::from_world_position(player.translation.truncate());
 
-    if grid_pos != grid_level_selection.0 {
-        *grid_level_selection = GridLevelSelection(grid_pos);
+    match levels.room_at(grid_pos) {
+        Some(room) if room.origin != grid_level_selection.0 => {
+            *grid_level_selection = GridLevelSelection(room.origin);
+        }
+        Some(_) => {}
+        None => {
+            commands.trigger(PlayerDied);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use bevy_ecs_ldtk::ldtk::LdtkJson;
+
     use super::*;
 
+    #[derive(Resource, Default)]
+    struct Deaths(u32);
+
+    fn room(i: usize, origin: IVec2, size: IVec2) -> Room {
+        Room {
+            iid: LevelIid::new(i.to_string()),
+            origin: GridCoords(origin),
+            size,
+        }
+    }
+
+    /// L-shaped world of three levels: (0, 0), (1, 0) and (0, 1).
+    fn l_shaped_levels() -> GridvaniaLevels {
+        let levels = [ivec2(0, 0), ivec2(1, 0), ivec2(0, 1)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, coords)| (GridCoords(coords), room(i, coords, IVec2::ONE)))
+            .collect();
+        GridvaniaLevels(levels)
+    }
+
+    /// Levels of the GridVania world of the game.
+    fn game_levels() -> GridvaniaLevels {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/world.ldtk");
+        let json = std::fs::read_to_string(path).unwrap();
+        let project = serde_json::from_str::<LdtkJson>(&json).unwrap();
+        GridvaniaLevels::from_levels(&project.levels)
+    }
+
+    fn world_app(levels: GridvaniaLevels) -> App {
+        let mut app = App::new();
+        app.insert_resource(levels)
+            .init_resource::<GridLevelSelection>()
+            .init_resource::<Deaths>()
+            .add_observer(|_: On<PlayerDied>, mut deaths: ResMut<Deaths>| deaths.0 += 1)
+            .add_systems(Update, level_selection_follow_player);
+        app
+    }
+
+    #[test]
+    fn test_walk_off_every_edge() {
+        let mut app = world_app(game_levels());
+        let directions = [ivec2(1, 0), ivec2(-1, 0), ivec2(0, 1), ivec2(0, -1)];
+
+        let levels = app.world().resource::<GridvaniaLevels>().0.clone();
+        let mut edges = 0;
+        for (coords, room) in &levels {
+            for direction in directions {
+                let outside = GridCoords(coords.get() + direction);
+                if levels.contains_key(&outside) {
+                    continue;
+                }
+                edges += 1;
+
+                // Walk from the center of the cell until over the edge
+                let mut position = coords.center();
+                let step = 8.0 * direction.as_vec2() * vec2(1., -1.);
+                let player = app
+                    .world_mut()
+                    .spawn((Player, Transform::from_translation(position.extend(0.))))
+                    .id();
+                *app.world_mut().resource_mut::<GridLevelSelection>() =
+                    GridLevelSelection(room.origin);
+                app.world_mut().resource_mut::<Deaths>().0 = 0;
+
+                while GridCoords::from_world_position(position) != outside {
+                    assert_eq!(
+                        app.world().resource::<Deaths>().0,
+                        0,
+                        "The player should be alive in {coords:?}"
+                    );
+                    position += step;
+                    app.world_mut()
+                        .entity_mut(player)
+                        .get_mut::<Transform>()
+                        .unwrap()
+                        .translation = position.extend(0.);
+                    app.update();
+                }
+
+                assert_eq!(app.world().resource::<Deaths>().0, 1);
+                assert_eq!(app.world().resource::<GridLevelSelection>().0, room.origin);
+
+                app.world_mut().despawn(player);
+            }
+        }
+
+        // The world has at least four borders
+        assert!(edges >= 4);
+    }
+
+    #[test]
+    fn test_walk_between_levels() {
+        let mut app = world_app(l_shaped_levels());
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_translation(GridCoords(ivec2(0, 0)).center().extend(0.)),
+            ))
+            .id();
+        app.update();
+
+        app.world_mut()
+            .entity_mut(player)
+            .get_mut::<Transform>()
+            .unwrap()
+            .translation = GridCoords(ivec2(1, 0)).center().extend(0.);
+        app.update();
+
+        assert_eq!(app.world().resource::<Deaths>().0, 0);
+        assert_eq!(
+            app.world().resource::<GridLevelSelection>().0,
+            GridCoords(ivec2(1, 0))
+        );
+    }
+
+    #[test]
+    fn test_walk_in_large_room() {
+        let mut app = world_app(l_shaped_levels());
+        // A 2x2 hall below the L-shape
+        let hall = room(3, ivec2(0, 2), ivec2(2, 2));
+        let mut levels = app.world_mut().resource_mut::<GridvaniaLevels>();
+        for coords in hall.cells().collect::<Vec<_>>() {
+            levels.0.insert(coords, hall.clone());
+        }
+        assert_eq!(levels.rooms().count(), 4);
+
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_translation(GridCoords(ivec2(0, 1)).center().extend(0.)),
+            ))
+            .id();
+        app.update();
+
+        for cell in [ivec2(0, 2), ivec2(1, 2), ivec2(1, 3), ivec2(0, 3)] {
+            app.world_mut()
+                .entity_mut(player)
+                .get_mut::<Transform>()
+                .unwrap()
+                .translation = GridCoords(cell).center().extend(0.);
+            app.update();
+
+            assert_eq!(
+                app.world().resource::<GridLevelSelection>().0,
+                hall.origin,
+                "The selection should stay on the hall origin in {cell}"
+            );
+        }
+        assert_eq!(app.world().resource::<Deaths>().0, 0);
+    }
+
+    #[test]
+    fn test_room_rect() {
+        let hall = room(0, ivec2(1, -1), ivec2(2, 1));
+        assert_eq!(hall.rect().min, vec2(512., 0.));
+        assert_eq!(hall.rect().max, vec2(1536., 288.));
+    }
+
     #[test]
     fn test_grid_coords_from_world_position() {
         // Typical case
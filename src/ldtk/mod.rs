@@ -2,21 +2,45 @@
 
 use std::str::FromStr;
 
-use bevy::{platform::collections::HashSet, prelude::*};
-use bevy_ecs_ldtk::{prelude::LdtkFields, *};
+use bevy::{ecs::system::SystemParam, platform::collections::HashSet, prelude::*};
+use bevy_ecs_ldtk::{
+    prelude::{LdtkFields, LdtkProject},
+    *,
+};
 
-use crate::theme::palette::*;
+use crate::{asset_collection::LevelAssets, theme::palette::*};
 
 pub mod entities;
 pub mod gridvania;
+pub mod transition;
 pub mod wall;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((entities::plugin, gridvania::plugin, wall::plugin));
+    app.add_plugins((
+        entities::plugin,
+        gridvania::plugin,
+        transition::plugin,
+        wall::plugin,
+    ));
+}
+
+/// Access to the raw data of the loaded [`LdtkProject`].
+#[derive(SystemParam)]
+pub struct LdtkProjectData<'w> {
+    level_assets: Option<Res<'w, LevelAssets>>,
+    ldtk_project_assets: Res<'w, Assets<LdtkProject>>,
+}
+
+impl LdtkProjectData<'_> {
+    /// Return the project, `None` until it is loaded.
+    pub fn get(&self) -> Option<&LdtkProject> {
+        let level_assets = self.level_assets.as_ref()?;
+        self.ldtk_project_assets.get(level_assets.world.id())
+    }
 }
 
 /// Describe all the color of a game object
-#[derive(Component, Reflect, Debug, Clone)]
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
 #[reflect(Component)]
 pub struct Tint(HashSet<GameColor>);
 
@@ -40,7 +64,6 @@ impl Tint {
     }
 
     /// Return a tint based on the `color` field, i.e. a single Enum.
-    #[allow(dead_code)]
     pub fn from_color_field(instance: &EntityInstance) -> Self {
         instance
             .get_maybe_enum_field("color")
@@ -56,7 +79,6 @@ impl Tint {
     }
 
     /// Return True if the Tint as the given color
-    #[allow(dead_code)]
     pub fn has_color(&self, color: GameColor) -> bool {
         self.0.contains(&color)
     }
@@ -65,6 +87,16 @@ impl Tint {
     pub fn share_color_with(&self, other: &Tint) -> bool {
         self.0.difference(&other.0).count() < self.0.len()
     }
+
+    /// Add a color to the Tint, holding at most `max_colors` colors.
+    ///
+    /// A full Tint is reset to the new color.
+    pub fn mix(&mut self, color: GameColor, max_colors: usize) {
+        if self.0.len() >= max_colors && !self.0.contains(&color) {
+            self.0.clear();
+        }
+        self.0.insert(color);
+    }
 }
 
 impl From<Option<GameColor>> for Tint {
@@ -94,39 +126,66 @@ impl FromIterator<GameColor> for Tint {
     }
 }
 
-/// List of Color for game elements
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Hash)]
-pub enum GameColor {
-    White,
-    Grey,
-    Green,
-    Brown,
-    Orange,
-}
+/// Define [`GameColor`], its parsing and its display colors from a single list.
+///
+/// The variant names are the ones of the LDtk `Color` enum, so adding a color
+/// only requires a new line in the list.
+macro_rules! game_colors {
+    ($($variant:ident => $color:expr),* $(,)?) => {
+        /// List of Color for game elements
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, Reflect, Hash, serde::Serialize, serde::Deserialize,
+        )]
+        pub enum GameColor {
+            $($variant),*
+        }
 
-impl FromStr for GameColor {
-    type Err = String;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "White" => Ok(Self::White),
-            "Grey" => Ok(Self::Grey),
-            "Green" => Ok(Self::Green),
-            "Brown" => Ok(Self::Brown),
-            "Orange" => Ok(Self::Orange),
-            _ => Err(format!("Cannot parse {s} as GameColor.")),
+        impl FromStr for GameColor {
+            type Err = String;
+
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                match s {
+                    $(stringify!($variant) => Ok(Self::$variant),)*
+                    _ => Err(format!("Cannot parse {s} as GameColor.")),
+                }
+            }
         }
-    }
+
+        impl GameColor {
+            pub fn color(&self) -> Color {
+                match self {
+                    $(Self::$variant => $color),*
+                }
+            }
+        }
+    };
 }
 
-impl GameColor {
-    pub fn color(&self) -> Color {
-        match self {
-            Self::White => WHITE,
-            Self::Grey => GREY,
-            Self::Green => GREEN,
-            Self::Brown => BROWN,
-            Self::Orange => ORANGE,
+game_colors! {
+    White => WHITE,
+    Grey => GREY,
+    Green => GREEN,
+    Brown => BROWN,
+    Orange => ORANGE,
+    Red => RED,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::{TypeInfo, Typed};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_every_game_color() {
+        let TypeInfo::Enum(info) = GameColor::type_info() else {
+            panic!("GameColor should be an enum.");
+        };
+
+        for name in info.variant_names() {
+            let game_color = name.parse::<GameColor>().unwrap();
+            assert_eq!(format!("{game_color:?}"), *name);
         }
+        assert!("Blue".parse::<GameColor>().is_err());
     }
 }
@@ -0,0 +1,250 @@
+//! Transitions between the rooms of the GridVania world.
+//!
+//! When [`GridLevelSelection`] changes during gameplay, the player and the
+//! physics are frozen while the camera pans to the new room, optionally behind
+//! a fade or a wipe. The player resumes once the destination level is spawned,
+//! then [`RoomEntered`] is triggered.
+
+use avian2d::prelude::{Physics, PhysicsTime};
+use bevy::{platform::collections::HashSet, prelude::*};
+use bevy_ecs_ldtk::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems, PausableSystems,
+    ldtk::gridvania::{GridCoords, GridLevelSelection, GridvaniaLevels},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RoomTransitionSettings>();
+    app.init_resource::<SpawnedLevels>();
+    app.init_resource::<LastRoom>();
+
+    app.add_systems(Update, track_spawned_levels);
+    app.add_systems(
+        Update,
+        (
+            start_room_transition.run_if(resource_changed::<GridLevelSelection>),
+            update_room_transition.run_if(resource_exists::<RoomTransition>),
+        )
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), cancel_room_transition);
+}
+
+/// Screen effect played during a room transition.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionEffect {
+    /// Only the camera pans
+    None,
+    /// The screen fades to black and back
+    #[default]
+    Fade,
+    /// A black screen sweeps in the direction of the movement
+    Wipe,
+}
+
+impl TransitionEffect {
+    pub const ALL: [Self; 3] = [Self::None, Self::Fade, Self::Wipe];
+
+    /// Return the next effect, looping back to the first one.
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|effect| *effect == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Return the previous effect, looping back to the last one.
+    pub fn previous(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|effect| *effect == self)
+            .unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+impl std::fmt::Display for TransitionEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            TransitionEffect::None => "None",
+            TransitionEffect::Fade => "Fade",
+            TransitionEffect::Wipe => "Wipe",
+        };
+        f.write_str(text)
+    }
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct RoomTransitionSettings {
+    /// Minimal duration of the freeze in sec.
+    pub duration: f32,
+    pub effect: TransitionEffect,
+}
+
+impl Default for RoomTransitionSettings {
+    fn default() -> Self {
+        Self {
+            duration: 0.4,
+            effect: TransitionEffect::default(),
+        }
+    }
+}
+
+/// Event triggered when the player resumes in a new room.
+#[derive(Event, Debug)]
+pub struct RoomEntered {
+    pub to: GridCoords,
+}
+
+/// Room transition in progress, the player is frozen while it exists.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct RoomTransition {
+    from: GridCoords,
+    to: GridCoords,
+    timer: Timer,
+}
+
+/// Levels whose entities are all spawned.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+struct SpawnedLevels(HashSet<LevelIid>);
+
+/// Room of the player when the last transition started.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+struct LastRoom(Option<GridCoords>);
+
+/// Full screen overlay animated during a room transition.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct TransitionOverlay;
+
+fn track_spawned_levels(
+    mut level_events: MessageReader<LevelEvent>,
+    mut spawned_levels: ResMut<SpawnedLevels>,
+) {
+    for level_event in level_events.read() {
+        match level_event {
+            LevelEvent::Transformed(iid) => {
+                spawned_levels.0.insert(iid.clone());
+            }
+            LevelEvent::Despawned(iid) => {
+                spawned_levels.0.remove(iid);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn start_room_transition(
+    mut commands: Commands,
+    grid_level_selection: Res<GridLevelSelection>,
+    mut last_room: ResMut<LastRoom>,
+    settings: Res<RoomTransitionSettings>,
+    mut physics_time: ResMut<Time<Physics>>,
+    overlays: Query<Entity, With<TransitionOverlay>>,
+) {
+    // The first room of the session has no transition
+    let Some(from) = last_room.0.replace(grid_level_selection.0) else {
+        return;
+    };
+    if from == grid_level_selection.0 {
+        return;
+    }
+
+    physics_time.pause();
+    commands.insert_resource(RoomTransition {
+        from,
+        to: grid_level_selection.0,
+        timer: Timer::from_seconds(settings.duration, TimerMode::Once),
+    });
+
+    for overlay in &overlays {
+        commands.entity(overlay).despawn();
+    }
+    if settings.effect != TransitionEffect::None {
+        commands.spawn((
+            Name::new("Transition Overlay"),
+            TransitionOverlay,
+            Node {
+                position_type: PositionType::Absolute,
+                width: percent(100),
+                height: percent(100),
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.0)),
+            GlobalZIndex(1),
+            Pickable::IGNORE,
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn update_room_transition(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut transition: ResMut<RoomTransition>,
+    settings: Res<RoomTransitionSettings>,
+    levels: If<Res<GridvaniaLevels>>,
+    spawned_levels: Res<SpawnedLevels>,
+    mut physics_time: ResMut<Time<Physics>>,
+    overlays: Query<(Entity, &mut Node, &mut BackgroundColor), With<TransitionOverlay>>,
+) {
+    transition.timer.tick(time.delta());
+    let progress = transition.timer.fraction();
+    let direction = (transition.to.get() - transition.from.get()).signum();
+
+    let level_spawned = levels
+        .get_level_at(transition.to)
+        .is_some_and(|iid| spawned_levels.0.contains(&iid));
+    let finished = transition.timer.is_finished() && level_spawned;
+
+    for (entity, mut node, mut background) in overlays {
+        if finished {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        match settings.effect {
+            TransitionEffect::None => {}
+            TransitionEffect::Fade => {
+                let alpha = 1.0 - (2.0 * progress - 1.0).abs();
+                background.0 = Color::BLACK.with_alpha(alpha);
+            }
+            TransitionEffect::Wipe => {
+                // Sweep from one side of the screen to the other, the grid
+                // y axis points down like the UI one
+                let offset = (200.0 * progress - 100.0) * direction.as_vec2();
+                background.0 = Color::BLACK;
+                node.left = percent(-offset.x);
+                node.top = percent(-offset.y);
+            }
+        }
+    }
+
+    if finished {
+        physics_time.unpause();
+        commands.remove_resource::<RoomTransition>();
+        commands.trigger(RoomEntered { to: transition.to });
+    }
+}
+
+fn cancel_room_transition(
+    mut commands: Commands,
+    mut last_room: ResMut<LastRoom>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    last_room.0 = None;
+    physics_time.unpause();
+    commands.remove_resource::<RoomTransition>();
+}
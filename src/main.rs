@@ -8,10 +8,15 @@ mod audio;
 mod camera;
 #[cfg(feature = "dev")]
 mod dev_tools;
+mod hud;
+mod input;
 mod ldtk;
 mod menus;
 mod player;
+mod save;
 mod screens;
+mod settings;
+mod storage;
 mod theme;
 
 use avian2d::{PhysicsPlugins, prelude::PhysicsLayer};
@@ -67,10 +72,14 @@ impl Plugin for AppPlugin {
             camera::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
+            hud::plugin,
+            input::plugin,
             ldtk::plugin,
             menus::plugin,
             player::plugin,
+            save::plugin,
             screens::plugin,
+            settings::plugin,
             theme::plugin,
         ));
 
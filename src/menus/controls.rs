@@ -0,0 +1,186 @@
+//! The controls menu, to rebind the keys of each [`Action`].
+
+use bevy::{ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    input::{Action, InputBindings},
+    menus::Menu,
+    theme::{focus::FocusNavigation, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RebindingAction>();
+    app.init_resource::<RebindConflict>();
+    // The pressed key is bound, it must not move the focus
+    app.configure_sets(Update, FocusNavigation.run_if(not(is_rebinding)));
+
+    app.add_systems(OnEnter(Menu::Controls), spawn_controls_menu);
+    app.add_systems(OnExit(Menu::Controls), stop_rebinding);
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape).and(not(is_rebinding))),
+            rebind_key.run_if(is_rebinding),
+            update_binding_labels,
+        )
+            .chain()
+            .run_if(in_state(Menu::Controls)),
+    );
+}
+
+/// The [`Action`] waiting for a new key, if any.
+#[derive(Resource, Debug, Default)]
+struct RebindingAction(Option<Action>);
+
+/// The [`Action`] already bound to the last key pressed while rebinding.
+#[derive(Resource, Debug, Default)]
+struct RebindConflict(Option<Action>);
+
+fn is_rebinding(rebinding: Res<RebindingAction>) -> bool {
+    rebinding.0.is_some()
+}
+
+fn spawn_controls_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Controls Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Controls),
+        children![
+            widget::header("Controls"),
+            (
+                Name::new("Bindings"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(10),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(Action::ALL.into_iter().map(binding_row))),
+            ),
+            widget::button("Reset", reset_bindings),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn binding_row(action: Action) -> impl Bundle {
+    (
+        Name::new("Binding Row"),
+        Node {
+            display: Display::Grid,
+            column_gap: px(30),
+            align_items: AlignItems::Center,
+            grid_template_columns: RepeatedGridTrack::px(3, 200.0),
+            ..default()
+        },
+        children![
+            (
+                widget::label(action.to_string()),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                },
+            ),
+            (widget::label(""), BindingLabel(action)),
+            widget::button("Change", start_rebinding(action)),
+        ],
+    )
+}
+
+fn start_rebinding(
+    action: Action,
+) -> impl Fn(On<Pointer<Click>>, ResMut<RebindingAction>, ResMut<RebindConflict>) {
+    move |_: On<Pointer<Click>>,
+          mut rebinding: ResMut<RebindingAction>,
+          mut conflict: ResMut<RebindConflict>| {
+        rebinding.0 = Some(action);
+        conflict.0 = None;
+    }
+}
+
+fn stop_rebinding(mut rebinding: ResMut<RebindingAction>, mut conflict: ResMut<RebindConflict>) {
+    rebinding.0 = None;
+    conflict.0 = None;
+}
+
+/// Bind the first pressed key as the primary key of the [`RebindingAction`].
+/// Escape cancels the rebinding, a key used by another action is refused.
+fn rebind_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<RebindingAction>,
+    mut conflict: ResMut<RebindConflict>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    // Ignore the key used to start the rebinding, e.g. Enter on the focused button
+    if rebinding.is_changed() {
+        return;
+    }
+
+    let Some(&key) = keyboard.get_just_pressed().next() else {
+        return;
+    };
+
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+    if key == KeyCode::Escape {
+        rebinding.0 = None;
+        conflict.0 = None;
+        return;
+    }
+
+    match bindings.rebind(action, key) {
+        Ok(()) => {
+            rebinding.0 = None;
+            conflict.0 = None;
+        }
+        Err(other) => conflict.0 = Some(other),
+    }
+}
+
+fn reset_bindings(_: On<Pointer<Click>>, mut bindings: ResMut<InputBindings>) {
+    *bindings = InputBindings::default();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BindingLabel(Action);
+
+fn update_binding_labels(
+    bindings: Res<InputBindings>,
+    rebinding: Res<RebindingAction>,
+    conflict: Res<RebindConflict>,
+    labels: Query<(&mut Text, &BindingLabel)>,
+) {
+    for (mut text, binding_label) in labels {
+        text.0 = if rebinding.0 == Some(binding_label.0) {
+            match conflict.0 {
+                Some(other) => format!("Used by {other}..."),
+                None => "Press a key...".to_string(),
+            }
+        } else {
+            bindings
+                .keys(binding_label.0)
+                .iter()
+                .map(|key| key_label(*key))
+                .collect::<Vec<_>>()
+                .join(" / ")
+        };
+    }
+}
+
+/// Short name of a key, e.g. `A` for [`KeyCode::KeyA`].
+fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
@@ -2,7 +2,14 @@
 
 use bevy::{ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{ldtk::gridvania::GridLevelSelection, menus::Menu, theme::prelude::*};
+use crate::{
+    ldtk::{
+        entities::{museum::Museum, object::ObjectType},
+        gridvania::GridLevelSelection,
+    },
+    menus::Menu,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
@@ -12,7 +19,11 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn spawn_credits_menu(mut commands: Commands, mut level_selection: ResMut<GridLevelSelection>) {
+fn spawn_credits_menu(
+    mut commands: Commands,
+    mut level_selection: ResMut<GridLevelSelection>,
+    museum: Res<Museum>,
+) {
     *level_selection = GridLevelSelection::new((0, -1));
 
     commands.spawn((
@@ -24,6 +35,8 @@ fn spawn_credits_menu(mut commands: Commands, mut level_selection: ResMut<GridLe
             created_by(),
             widget::header("Assets"),
             assets(),
+            widget::header("Museum"),
+            museum_completion(&museum),
             widget::button("Back", go_back_on_click),
         ],
     ));
@@ -42,7 +55,21 @@ fn assets() -> impl Bundle {
     ])
 }
 
-fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
+fn museum_completion(museum: &Museum) -> impl Bundle {
+    let (displayed, total) = museum.completion();
+    let mut content = ObjectType::ALL
+        .into_iter()
+        .map(|object_type| {
+            let (displayed, total) = museum.completion_of(object_type);
+            [format!("{object_type:?}"), format!("{displayed} / {total}")]
+        })
+        .collect::<Vec<_>>();
+    content.push(["Total".to_string(), format!("{displayed} / {total}")]);
+
+    grid(content)
+}
+
+fn grid(content: Vec<[impl Into<String> + Send + Sync + 'static; 2]>) -> impl Bundle {
     (
         Name::new("Grid"),
         Node {
@@ -0,0 +1,126 @@
+//! The world map menu.
+//!
+//! Each visited room is drawn over its cells, colored by its dominant
+//! `Colortile` color, the current room is highlighted.
+
+use bevy::{ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    input::Action,
+    ldtk::gridvania::{GridCoords, GridLevelSelection, GridvaniaLevels, Room, RoomColors},
+    menus::Menu,
+    save::GameProgress,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Map), spawn_map_menu);
+    app.add_systems(
+        Update,
+        close_menu.run_if(
+            in_state(Menu::Map)
+                .and(input_just_pressed(Action::Map).or(input_just_pressed(Action::Pause))),
+        ),
+    );
+}
+
+/// Size of a grid cell in pixel, with the aspect ratio of a level.
+const CELL_SIZE: Vec2 = Vec2::new(48.0, 27.0);
+
+/// Space between two cells in pixel.
+const CELL_GAP: f32 = 4.0;
+
+fn spawn_map_menu(
+    mut commands: Commands,
+    levels: Res<GridvaniaLevels>,
+    room_colors: Res<RoomColors>,
+    grid_level_selection: Res<GridLevelSelection>,
+    progress: Res<GameProgress>,
+) {
+    let visited_rooms = levels
+        .rooms()
+        .filter(|room| {
+            progress.visited_rooms.contains(&room.origin) || room.origin == grid_level_selection.0
+        })
+        .collect::<Vec<_>>();
+
+    commands.spawn((
+        widget::ui_root("Map Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Map),
+        children![
+            widget::header("Map"),
+            map_grid(&levels, &room_colors, visited_rooms, grid_level_selection.0),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn map_grid(
+    levels: &GridvaniaLevels,
+    room_colors: &RoomColors,
+    visited_rooms: Vec<&Room>,
+    current: GridCoords,
+) -> impl Bundle {
+    // Bounds of the whole world, so the map layout does not depend on the visited rooms
+    let min = levels.coords().fold(IVec2::MAX, |min, c| min.min(c.get()));
+    let max = levels.coords().fold(IVec2::MIN, |max, c| max.max(c.get()));
+    let size = (max - min + IVec2::ONE).max(IVec2::ZERO).as_vec2();
+
+    let cells = visited_rooms
+        .into_iter()
+        .map(|room| {
+            let color = room_colors
+                .get(room.origin)
+                .map(|color| color.color())
+                .unwrap_or(ui_palette::LABEL_TEXT);
+            room_cell(
+                (room.origin.get() - min).as_vec2(),
+                room.size.as_vec2(),
+                color,
+                room.origin == current,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    (
+        Name::new("Map Grid"),
+        Node {
+            width: px(size.x * (CELL_SIZE.x + CELL_GAP)),
+            height: px(size.y * (CELL_SIZE.y + CELL_GAP)),
+            margin: UiRect::vertical(px(10)),
+            ..default()
+        },
+        Children::spawn(SpawnIter(cells.into_iter())),
+    )
+}
+
+fn room_cell(position: Vec2, size: Vec2, color: Color, current: bool) -> impl Bundle {
+    (
+        Name::new("Room Cell"),
+        Node {
+            position_type: PositionType::Absolute,
+            left: px(position.x * (CELL_SIZE.x + CELL_GAP)),
+            top: px(position.y * (CELL_SIZE.y + CELL_GAP)),
+            // Large rooms also cover the gaps between their cells
+            width: px(size.x * (CELL_SIZE.x + CELL_GAP) - CELL_GAP),
+            height: px(size.y * (CELL_SIZE.y + CELL_GAP) - CELL_GAP),
+            border: UiRect::all(px(if current { 3 } else { 1 })),
+            ..default()
+        },
+        BackgroundColor(color),
+        BorderColor::all(if current {
+            ui_palette::BUTTON_TEXT
+        } else {
+            ui_palette::BACKGROUND
+        }),
+    )
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
+
+fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
@@ -1,7 +1,9 @@
 //! The game's menus and transitions between them.
 
+mod controls;
 mod credits;
 mod main;
+mod map;
 mod pause;
 mod settings;
 
@@ -11,8 +13,10 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        controls::plugin,
         credits::plugin,
         main::plugin,
+        map::plugin,
         settings::plugin,
         pause::plugin,
     ));
@@ -25,5 +29,7 @@ pub enum Menu {
     Main,
     Credits,
     Settings,
+    Controls,
     Pause,
+    Map,
 }
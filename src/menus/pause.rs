@@ -20,12 +20,17 @@ fn spawn_pause_menu(mut commands: Commands) {
         children![
             widget::header("Game paused"),
             widget::button("Continue", close_menu),
+            widget::button("Map", open_map_menu),
             widget::button("Settings", open_settings_menu),
             widget::button("Quit to title", quit_to_title),
         ],
     ));
 }
 
+fn open_map_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Map);
+}
+
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
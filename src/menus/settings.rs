@@ -6,10 +6,10 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
 use crate::{
     audio::{AudioSettings, VolumeType},
-    ldtk::gridvania::GridLevelSelection,
+    ldtk::{gridvania::GridLevelSelection, transition::RoomTransitionSettings},
     menus::Menu,
     screens::Screen,
-    theme::prelude::*,
+    theme::{focus::HorizontalAdjust, prelude::*},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -19,7 +19,10 @@ pub(super) fn plugin(app: &mut App) {
         go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
     );
 
-    app.add_systems(Update, update_volume_label.run_if(in_state(Menu::Settings)));
+    app.add_systems(
+        Update,
+        (update_volume_label, update_transition_label).run_if(in_state(Menu::Settings)),
+    );
 }
 
 fn spawn_settings_menu(mut commands: Commands, mut level_selection: ResMut<GridLevelSelection>) {
@@ -32,6 +35,7 @@ fn spawn_settings_menu(mut commands: Commands, mut level_selection: ResMut<GridL
         children![
             widget::header("Settings"),
             settings_grid(),
+            widget::button("Controls", open_controls_menu),
             widget::button("Back", go_back_on_click),
         ],
     ));
@@ -54,6 +58,14 @@ fn settings_grid() -> impl Bundle {
             volume_widget(VolumeType::Music),
             volume_label(VolumeType::Sfx),
             volume_widget(VolumeType::Sfx),
+            (
+                widget::label("Room Transition"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                },
+            ),
+            transition_widget(),
         ],
     )
 }
@@ -71,6 +83,7 @@ fn volume_label(volume_type: VolumeType) -> impl Bundle {
 fn volume_widget(volume_type: VolumeType) -> impl Bundle {
     (
         Name::new("Volume Widget"),
+        HorizontalAdjust,
         Node {
             justify_self: JustifySelf::Start,
             ..default()
@@ -122,6 +135,59 @@ fn update_volume_label(
     }
 }
 
+fn transition_widget() -> impl Bundle {
+    (
+        Name::new("Transition Widget"),
+        HorizontalAdjust,
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_transition_effect),
+            (
+                Name::new("Current Transition"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), TransitionLabel)],
+            ),
+            widget::button_small(">", next_transition_effect),
+        ],
+    )
+}
+
+fn previous_transition_effect(
+    _: On<Pointer<Click>>,
+    mut transition_settings: ResMut<RoomTransitionSettings>,
+) {
+    transition_settings.effect = transition_settings.effect.previous();
+}
+
+fn next_transition_effect(
+    _: On<Pointer<Click>>,
+    mut transition_settings: ResMut<RoomTransitionSettings>,
+) {
+    transition_settings.effect = transition_settings.effect.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TransitionLabel;
+
+fn update_transition_label(
+    transition_settings: Res<RoomTransitionSettings>,
+    mut label: Single<&mut Text, With<TransitionLabel>>,
+) {
+    label.0 = transition_settings.effect.to_string();
+}
+
+fn open_controls_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Controls);
+}
+
 fn go_back_on_click(
     _: On<Pointer<Click>>,
     screen: Res<State<Screen>>,
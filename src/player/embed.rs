@@ -0,0 +1,286 @@
+//! Safety check preventing tint changes that embed the player inside walls.
+//!
+//! Walls sharing a color with the player are intangible. When the [`Tint`]
+//! changes while the player stands inside such a wall, the wall would become
+//! solid around them. The change is then reverted and the player blinks.
+
+use avian2d::{math::AdjustPrecision, prelude::*};
+use bevy::prelude::*;
+
+use crate::{
+    ldtk::{Tint, wall::Wall},
+    player::{Player, physics::overlaps_solid_wall},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(start_reject_blink);
+    app.add_systems(Update, update_reject_blink);
+}
+
+/// Last accepted tint of the player.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct PreviousTint(pub Tint);
+
+/// Event triggered when a tint change is rejected.
+#[derive(Event, Debug)]
+pub struct TintRejected;
+
+/// Revert the player tint if the new one makes an overlapping wall solid.
+pub(super) fn guard_tint_change(
+    mut commands: Commands,
+    player: Single<
+        (&mut Tint, &mut PreviousTint, &Transform, &Collider),
+        (With<Player>, Changed<Tint>),
+    >,
+    walls: Query<&Tint, (With<Wall>, Without<Player>)>,
+    spatial_query: SpatialQuery,
+) {
+    let (mut tint, mut previous, transform, collider) = player.into_inner();
+    let position = transform.translation.xy().adjust_precision();
+
+    let checked = checked_tint(&previous.0, &tint, |tint| {
+        overlaps_solid_wall(&spatial_query, collider, position, tint, &walls)
+    });
+
+    match checked {
+        Some(accepted) => previous.0 = accepted,
+        None => {
+            *tint = previous.0.clone();
+            commands.trigger(TintRejected);
+        }
+    }
+}
+
+/// Return the tint to accept when changing from `previous` to `new`, or `None` if
+/// the change must be reverted.
+///
+/// A change is rejected only if it embeds the player: when already stuck with
+/// the previous tint, reverting would not help.
+pub(super) fn checked_tint(
+    previous: &Tint,
+    new: &Tint,
+    overlaps_solid_wall: impl Fn(&Tint) -> bool,
+) -> Option<Tint> {
+    if overlaps_solid_wall(new) && !overlaps_solid_wall(previous) {
+        None
+    } else {
+        Some(new.clone())
+    }
+}
+
+/// Blink of the player after a rejected tint change.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct RejectBlink(Timer);
+
+/// Interval between two blinks in sec.
+const BLINK_INTERVAL: f32 = 0.05;
+
+fn start_reject_blink(
+    _: On<TintRejected>,
+    mut commands: Commands,
+    player: Single<Entity, With<Player>>,
+) {
+    commands
+        .entity(*player)
+        .insert(RejectBlink(Timer::from_seconds(0.3, TimerMode::Once)));
+}
+
+fn update_reject_blink(
+    time: Res<Time>,
+    mut commands: Commands,
+    players: Query<(Entity, &mut RejectBlink, &mut Visibility), With<Player>>,
+) {
+    for (entity, mut blink, mut visibility) in players {
+        blink.0.tick(time.delta());
+
+        if blink.0.is_finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<RejectBlink>();
+        } else if ((blink.0.elapsed_secs() / BLINK_INTERVAL) as u32).is_multiple_of(2) {
+            *visibility = Visibility::Hidden;
+        } else {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{scene::ScenePlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+    use crate::{
+        GameLayer,
+        ldtk::GameColor,
+        player::timed_paint::{TintHistory, wear_off_paint},
+    };
+
+    #[derive(Resource, Default)]
+    struct Rejections(u32);
+
+    /// App with the player standing inside an intangible green wall.
+    fn embedded_player_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            AssetPlugin::default(),
+            ScenePlugin,
+            PhysicsPlugins::default(),
+        ))
+        .init_asset::<Mesh>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            1.0 / 60.0,
+        )))
+        .init_resource::<Rejections>()
+        .add_observer(|_: On<TintRejected>, mut rejections: ResMut<Rejections>| rejections.0 += 1)
+        .add_systems(Update, guard_tint_change);
+        // Avian inserts some of its resources when the plugins finish
+        app.finish();
+
+        app.world_mut().spawn((
+            Wall,
+            Tint::from_color(GameColor::Green),
+            RigidBody::Static,
+            Collider::rectangle(64.0, 64.0),
+            CollisionLayers::new(GameLayer::Ground, [GameLayer::Player]),
+            Transform::default(),
+        ));
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Tint::from_color(GameColor::Green),
+                PreviousTint(Tint::from_color(GameColor::Green)),
+                Collider::capsule(6.0, 4.0),
+                CollisionLayers::new(GameLayer::Player, [GameLayer::Ground]),
+                Transform::default(),
+            ))
+            .id();
+
+        // Let the physics register the colliders for the spatial queries
+        for _ in 0..5 {
+            app.update();
+        }
+        (app, player)
+    }
+
+    #[test]
+    fn test_guard_reverts_embedding_tint() {
+        let (mut app, player) = embedded_player_app();
+
+        *app.world_mut().get_mut::<Tint>(player).unwrap() = Tint::from_color(GameColor::Brown);
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Tint>(player),
+            Some(&Tint::from_color(GameColor::Green))
+        );
+        assert_eq!(app.world().resource::<Rejections>().0, 1);
+    }
+
+    #[test]
+    fn test_guard_accepts_mixed_tint() {
+        let (mut app, player) = embedded_player_app();
+
+        let mixed = Tint::from(vec![GameColor::Green, GameColor::Brown]);
+        *app.world_mut().get_mut::<Tint>(player).unwrap() = mixed.clone();
+        app.update();
+
+        assert_eq!(app.world().get::<Tint>(player), Some(&mixed));
+        assert_eq!(app.world().resource::<Rejections>().0, 0);
+    }
+
+    #[test]
+    fn test_blocked_paint_revert_waits_without_blinking() {
+        let (mut app, player) = embedded_player_app();
+        app.add_observer(start_reject_blink)
+            .add_systems(Update, (wear_off_paint, update_reject_blink));
+
+        // The grey tint under the worn off paint would embed the player
+        let mut history = TintHistory::default();
+        history.push(Tint::from_color(GameColor::Grey), GameColor::Green, 0.0);
+        app.world_mut()
+            .entity_mut(player)
+            .insert((history, Visibility::Inherited));
+
+        for _ in 0..10 {
+            app.update();
+            assert_eq!(
+                app.world().get::<Visibility>(player),
+                Some(&Visibility::Inherited)
+            );
+        }
+        assert_eq!(
+            app.world().get::<Tint>(player),
+            Some(&Tint::from_color(GameColor::Green))
+        );
+        assert_eq!(app.world().resource::<Rejections>().0, 0);
+
+        // The revert happens once the player left the wall
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation
+            .x = 100.0;
+        for _ in 0..20 {
+            app.update();
+        }
+        assert_eq!(
+            app.world().get::<Tint>(player),
+            Some(&Tint::from_color(GameColor::Grey))
+        );
+        assert!(
+            app.world()
+                .get::<TintHistory>(player)
+                .unwrap()
+                .current()
+                .is_none()
+        );
+    }
+
+    /// The player stands inside a green wall.
+    fn overlaps_green_wall(tint: &Tint) -> bool {
+        !tint.has_color(GameColor::Green)
+    }
+
+    #[test]
+    fn test_reject_embedding_tint() {
+        let previous = Tint::from_color(GameColor::Green);
+        let new = Tint::from_color(GameColor::Brown);
+
+        assert!(checked_tint(&previous, &new, overlaps_green_wall).is_none());
+    }
+
+    #[test]
+    fn test_accept_tint_keeping_wall_intangible() {
+        let previous = Tint::from_color(GameColor::Green);
+        let new = Tint::from(vec![GameColor::Green, GameColor::Brown]);
+
+        assert!(
+            checked_tint(&previous, &new, overlaps_green_wall).is_some_and(|tint| {
+                tint.has_color(GameColor::Brown) && tint.has_color(GameColor::Green)
+            })
+        );
+    }
+
+    #[test]
+    fn test_accept_tint_outside_walls() {
+        let previous = Tint::from_color(GameColor::Green);
+        let new = Tint::from_color(GameColor::Brown);
+
+        assert!(checked_tint(&previous, &new, |_| false).is_some());
+    }
+
+    #[test]
+    fn test_accept_tint_when_already_stuck() {
+        let previous = Tint::from_color(GameColor::Grey);
+        let new = Tint::from_color(GameColor::Brown);
+
+        assert!(checked_tint(&previous, &new, overlaps_green_wall).is_some());
+    }
+}
@@ -1,40 +1,70 @@
 //! Code for the player character
 
 use bevy::prelude::*;
-use bevy_ecs_ldtk::assets::LdtkProject;
 
 use crate::{
-    PausableSystems,
-    asset_collection::LevelAssets,
-    ldtk::GameColor,
+    AppSystems, PausableSystems,
+    ldtk::{
+        GameColor, LdtkProjectData, Tint, entities::droplet::ColorMixing,
+        transition::RoomTransition,
+    },
     player::{
-        movement::CharacterMovementBundle, physics::CharacterPhysicsBundle,
-        visual::CharacterVisualBundle,
+        embed::PreviousTint,
+        movement::{CharacterMovementBundle, MovementParams},
+        palette::ColorPalette,
+        physics::CharacterPhysicsBundle,
+        respawn::{Dying, RespawnPoint},
+        timed_paint::TintHistory,
+        visual::{CharacterVisualBundle, TintMaterial},
     },
+    save::GameProgress,
     screens::Screen,
 };
 
+pub mod embed;
 pub mod movement;
+pub mod palette;
 pub mod physics;
+pub mod respawn;
+pub mod timed_paint;
 pub mod visual;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((movement::plugin, physics::plugin, visual::plugin));
+    app.add_plugins((
+        embed::plugin,
+        movement::plugin,
+        palette::plugin,
+        physics::plugin,
+        respawn::plugin,
+        timed_paint::plugin,
+        visual::plugin,
+    ));
 
     app.add_systems(OnEnter(Screen::Gameplay), spawn_character);
     app.add_systems(
         Update,
         (
             movement::apply_gravity,
+            movement::apply_wall_slide,
+            embed::guard_tint_change.after(AppSystems::Update),
             physics::run_move_and_slide,
             physics::update_grounded,
+            physics::update_touching_wall,
             movement::update_coyote_timer,
+            movement::update_jump_buffer,
         )
             .chain()
-            .run_if(in_state(Screen::Gameplay))
+            .run_if(in_state(Screen::Gameplay).and(not(resource_exists::<RoomTransition>)))
             .in_set(PausableSystems),
     )
-    .add_systems(Update, movement::apply_movement);
+    .add_systems(
+        Update,
+        movement::apply_movement
+            .before(physics::run_move_and_slide)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(not(resource_exists::<RoomTransition>)),
+    );
 }
 
 #[derive(Component)]
@@ -42,16 +72,14 @@ pub struct Player;
 
 pub fn spawn_character(
     mut commands: Commands,
-    material_asets: ResMut<Assets<ColorMaterial>>,
+    material_asets: ResMut<Assets<TintMaterial>>,
     mesh_assets: ResMut<Assets<Mesh>>,
     maybe_player: Option<Single<Entity, With<Player>>>,
-    level_assets: Res<LevelAssets>,
-    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    project: LdtkProjectData,
+    progress: Res<GameProgress>,
 ) {
     // Find the unique Spawn entity
-    let ldtk_project = ldtk_project_assets
-        .get(level_assets.world.id())
-        .expect("Project should be loaded by then.");
+    let ldtk_project = project.get().expect("Project should be loaded by then.");
 
     let spawn_pos = ldtk_project
         .json_data()
@@ -68,19 +96,52 @@ pub fn spawn_character(
         .flatten()
         .unwrap_or(Vec2::new(445., -200.));
 
+    // Resume from the saved progress if any
+    let spawn_pos = progress.player_position.unwrap_or(spawn_pos);
+    let tint = if progress.tint.is_empty() {
+        Tint::from_color(GameColor::Grey)
+    } else {
+        Tint::from(progress.tint.clone())
+    };
+
     // Create the character bundle
     let shape = Capsule2d::new(8.0, 10.0);
     let bundle = (
         Player,
         CharacterVisualBundle::new(shape, GameColor::Grey, mesh_assets, material_asets),
-        CharacterMovementBundle::new(10.0, 4.0, 0.3, 0.3),
+        CharacterMovementBundle::new(MovementParams::default()),
         CharacterPhysicsBundle::new(shape),
         Transform::from_translation(spawn_pos.extend(3.0)),
     );
 
-    if let Some(player) = maybe_player {
-        commands.entity(*player).insert(bundle);
+    let mut player = match maybe_player {
+        Some(player) => commands.entity(*player),
+        None => commands.spawn_empty(),
+    };
+    let palette = ColorPalette::new(
+        [GameColor::Grey]
+            .into_iter()
+            .chain(progress.palette.iter().copied())
+            .chain(tint.get_colors()),
+        0.5,
+    );
+    player
+        .insert((
+            bundle,
+            palette,
+            PreviousTint(tint.clone()),
+            TintHistory::default(),
+        ))
+        .insert(tint.clone())
+        .remove::<Dying>();
+    if progress.color_mixing {
+        player.insert(ColorMixing::default());
     } else {
-        commands.spawn(bundle);
+        player.remove::<ColorMixing>();
     }
+
+    commands.insert_resource(RespawnPoint {
+        position: spawn_pos,
+        tint,
+    });
 }
@@ -1,7 +1,17 @@
-use avian2d::{math::Scalar, prelude::LinearVelocity};
+use avian2d::{
+    math::{Scalar, Vector},
+    prelude::LinearVelocity,
+};
 use bevy::{math::FloatPow, prelude::*};
 
-use crate::player::{Player, physics::Grounded};
+use crate::{
+    input::Action,
+    player::{
+        Player,
+        physics::{Grounded, TouchingWall, WallSide},
+        respawn::Dying,
+    },
+};
 
 pub(super) fn plugin(_app: &mut App) {}
 
@@ -12,23 +22,65 @@ const BLOCK_SIZE: Scalar = 16.0;
 #[reflect(Component)]
 pub struct MovementSpeed(pub Scalar);
 
+/// The horizontal acceleration used for character movement.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct MovementAcceleration {
+    /// Acceleration towards the movement speed when moving
+    pub acceleration: Scalar,
+    /// Deceleration towards rest when there is no input
+    pub deceleration: Scalar,
+    /// Factor applied to the acceleration and deceleration while in the air
+    pub air_control: Scalar,
+}
+
+impl MovementAcceleration {
+    /// Return the maximal change of horizontal velocity per second.
+    fn rate(&self, is_moving: bool, is_grounded: bool) -> Scalar {
+        let rate = if is_moving {
+            self.acceleration
+        } else {
+            self.deceleration
+        };
+
+        if is_grounded {
+            rate
+        } else {
+            rate * self.air_control
+        }
+    }
+}
+
 /// The velocity impulse for the jump.
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct JumpImpulse(pub Scalar);
 
+/// Factor applied to the upward velocity when the jump is released early.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct JumpCut(pub Scalar);
+
+/// Wall slide and wall jump parameters.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct WallMovement {
+    /// Maximal falling speed while holding toward a wall
+    pub slide_speed: Scalar,
+    /// Velocity of the wall jump, the horizontal part pushes away from the wall
+    pub jump_impulse: Vector,
+}
+
 /// The coyote timer of the Jump
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub struct CoyoteTimer(Timer);
 
-impl Default for CoyoteTimer {
-    fn default() -> Self {
-        Self(Timer::from_seconds(0.1, TimerMode::Once))
+impl CoyoteTimer {
+    fn new(duration: Scalar) -> Self {
+        Self(Timer::from_seconds(duration, TimerMode::Once))
     }
-}
 
-impl CoyoteTimer {
     fn can_jump(&self) -> bool {
         !self.0.is_finished()
     }
@@ -36,6 +88,36 @@ impl CoyoteTimer {
     fn reset_timer(&mut self) {
         self.0.reset();
     }
+
+    /// Prevent another jump until the timer is reset.
+    fn consume(&mut self) {
+        self.0.finish();
+    }
+}
+
+/// The jump buffer: remember a jump press for a short time before landing.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct JumpBuffer(Timer);
+
+impl JumpBuffer {
+    fn new(duration: Scalar) -> Self {
+        let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+        timer.finish();
+        Self(timer)
+    }
+
+    fn is_buffered(&self) -> bool {
+        !self.0.is_finished()
+    }
+
+    fn buffer(&mut self) {
+        self.0.reset();
+    }
+
+    fn consume(&mut self) {
+        self.0.finish();
+    }
 }
 
 /// The gravitational acceleration used for a character controller.
@@ -64,22 +146,90 @@ impl Default for GravityController {
 #[derive(Bundle)]
 pub struct CharacterMovementBundle {
     speed: MovementSpeed,
+    acceleration: MovementAcceleration,
     jump_impulse: JumpImpulse,
+    jump_cut: JumpCut,
     coyote_timer: CoyoteTimer,
+    jump_buffer: JumpBuffer,
+    wall_movement: WallMovement,
     gravity: GravityController,
 }
 
+/// Tuning of the character movement, see [`CharacterMovementBundle::new`].
+#[derive(Debug, Clone)]
+pub struct MovementParams {
+    /// Horizontal speed in block/sec
+    pub speed: Scalar,
+    /// Acceleration towards `speed` in block/sec²
+    pub acceleration: Scalar,
+    /// Deceleration towards rest in block/sec²
+    pub deceleration: Scalar,
+    /// Factor applied to the acceleration and deceleration while in the air
+    pub air_control: Scalar,
+    /// Height of a full jump in blocks
+    pub jump_height: Scalar,
+    /// Time to reach the top of a full jump in sec
+    pub jump_time: Scalar,
+    /// Time to fall from the top of a full jump in sec
+    pub fall_time: Scalar,
+    /// Factor applied to the upward velocity when the jump is released early
+    pub jump_cut: Scalar,
+    /// Window in sec where a jump is still accepted after leaving the ground
+    pub coyote_time: Scalar,
+    /// Window in sec where a jump press is remembered before landing
+    pub jump_buffer_time: Scalar,
+}
+
+impl Default for MovementParams {
+    fn default() -> Self {
+        Self {
+            speed: 10.0,
+            acceleration: 80.0,
+            deceleration: 100.0,
+            air_control: 0.6,
+            jump_height: 4.0,
+            jump_time: 0.3,
+            fall_time: 0.3,
+            jump_cut: 0.5,
+            coyote_time: 0.1,
+            jump_buffer_time: 0.1,
+        }
+    }
+}
+
 impl CharacterMovementBundle {
-    /// `speed` in block/sec and `jump_height` in blocks and `jump_time` in sec
-    pub fn new(speed: Scalar, jump_height: Scalar, jump_time: Scalar, fall_time: Scalar) -> Self {
+    pub fn new(params: MovementParams) -> Self {
+        let MovementParams {
+            speed,
+            acceleration,
+            deceleration,
+            air_control,
+            jump_height,
+            jump_time,
+            fall_time,
+            jump_cut,
+            coyote_time,
+            jump_buffer_time,
+        } = params;
         let jump_gravity = (2.0 * jump_height * BLOCK_SIZE) / jump_time.squared();
         let fall_gravity = (2.0 * jump_height * BLOCK_SIZE) / fall_time.squared();
         let jump_velocity = jump_gravity * jump_time;
 
         Self {
             speed: MovementSpeed(speed * BLOCK_SIZE),
+            acceleration: MovementAcceleration {
+                acceleration: acceleration * BLOCK_SIZE,
+                deceleration: deceleration * BLOCK_SIZE,
+                air_control,
+            },
             jump_impulse: JumpImpulse(jump_velocity),
-            coyote_timer: CoyoteTimer::default(),
+            jump_cut: JumpCut(jump_cut),
+            coyote_timer: CoyoteTimer::new(coyote_time),
+            jump_buffer: JumpBuffer::new(jump_buffer_time),
+            wall_movement: WallMovement {
+                slide_speed: 3.0 * BLOCK_SIZE,
+                jump_impulse: Vector::new(speed * BLOCK_SIZE, jump_velocity),
+            },
             gravity: GravityController {
                 jump_gravity,
                 fall_gravity,
@@ -92,7 +242,10 @@ impl CharacterMovementBundle {
 /// Applies he gravity to character controllers.
 pub(super) fn apply_gravity(
     time: Res<Time>,
-    mut controllers: Query<(&GravityController, &mut LinearVelocity), Without<Grounded>>,
+    mut controllers: Query<
+        (&GravityController, &mut LinearVelocity),
+        (Without<Grounded>, Without<Dying>),
+    >,
 ) {
     let delta_time = time.delta_secs();
     for (gravity, mut linear_velocity) in &mut controllers {
@@ -110,36 +263,93 @@ pub(super) fn apply_gravity(
     }
 }
 
+/// Caps the falling speed of characters holding toward the wall they touch.
+pub(super) fn apply_wall_slide(
+    input: Res<ButtonInput<Action>>,
+    mut controllers: Query<
+        (&WallMovement, &TouchingWall, &mut LinearVelocity),
+        (With<Player>, Without<Grounded>),
+    >,
+) {
+    for (wall_movement, touching_wall, mut linear_velocity) in &mut controllers {
+        let toward_wall = match touching_wall.0 {
+            WallSide::Left => Action::MoveLeft,
+            WallSide::Right => Action::MoveRight,
+        };
+
+        if input.pressed(toward_wall) && linear_velocity.y < -wall_movement.slide_speed {
+            linear_velocity.y = -wall_movement.slide_speed;
+        }
+    }
+}
+
 /// Responds to inputs and moves character controllers accordingly.
 pub(super) fn apply_movement(
-    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    input: Res<ButtonInput<Action>>,
     controller: Single<
         (
             &MovementSpeed,
+            &MovementAcceleration,
             &mut LinearVelocity,
             &JumpImpulse,
-            &CoyoteTimer,
+            &JumpCut,
+            &mut CoyoteTimer,
+            &mut JumpBuffer,
+            &WallMovement,
+            Option<&TouchingWall>,
             Has<Grounded>,
         ),
-        With<Player>,
+        (With<Player>, Without<Dying>),
     >,
 ) {
-    let (movement_speed, mut linear_velocity, jump_impulse, coyote_timer, is_grounded) =
-        controller.into_inner();
+    let (
+        movement_speed,
+        movement_acceleration,
+        mut linear_velocity,
+        jump_impulse,
+        jump_cut,
+        mut coyote_timer,
+        mut jump_buffer,
+        wall_movement,
+        touching_wall,
+        is_grounded,
+    ) = controller.into_inner();
+
+    if input.just_pressed(Action::Jump) {
+        jump_buffer.buffer();
+    }
 
-    if input.just_pressed(KeyCode::Space) && (is_grounded || coyote_timer.can_jump()) {
+    if jump_buffer.is_buffered() && (is_grounded || coyote_timer.can_jump()) {
         linear_velocity.y = jump_impulse.0;
+        jump_buffer.consume();
+        coyote_timer.consume();
+    } else if jump_buffer.is_buffered()
+        && let Some(touching_wall) = touching_wall
+    {
+        // Wall jump, pushing away from the wall
+        linear_velocity.x = -touching_wall.0.direction() * wall_movement.jump_impulse.x;
+        linear_velocity.y = wall_movement.jump_impulse.y;
+        jump_buffer.consume();
+    }
+
+    // Releasing the jump early cuts the ascent for short hops
+    if input.just_released(Action::Jump) && linear_velocity.y > 0.0 {
+        linear_velocity.y *= jump_cut.0;
     }
 
     let mut direction = 0;
-    if input.pressed(KeyCode::KeyA) {
+    if input.pressed(Action::MoveLeft) {
         direction += -1
     }
-    if input.pressed(KeyCode::KeyD) {
+    if input.pressed(Action::MoveRight) {
         direction += 1
     }
 
-    linear_velocity.x = (direction as Scalar) * movement_speed.0;
+    // Accelerate towards the target speed
+    let target_speed = (direction as Scalar) * movement_speed.0;
+    let max_delta = movement_acceleration.rate(direction != 0, is_grounded) * time.delta_secs();
+    linear_velocity.x += (target_speed - linear_velocity.x).clamp(-max_delta, max_delta);
 }
 
 /// Update the coyote timer every frame
@@ -155,3 +365,10 @@ pub(super) fn update_coyote_timer(
         }
     }
 }
+
+/// Update the jump buffer every frame
+pub(super) fn update_jump_buffer(time: Res<Time>, buffers: Query<&mut JumpBuffer>) {
+    for mut jump_buffer in buffers {
+        jump_buffer.0.tick(time.delta());
+    }
+}
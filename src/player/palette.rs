@@ -0,0 +1,185 @@
+//! Color palette of the player.
+//!
+//! Each droplet touched unlocks its color, and the player cycles through the
+//! unlocked colors with [`Action::SwitchColor`]. A mixed tint keeps its number
+//! of colors, each of them moving to the next unlocked color.
+
+use avian2d::{math::AdjustPrecision, prelude::*};
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_collection::AudioAssets,
+    audio::{AudioSettings, sound_effect},
+    input::Action,
+    ldtk::{GameColor, Tint, wall::Wall},
+    player::{
+        Player, embed::TintRejected, physics::overlaps_solid_wall, respawn::Dying,
+        timed_paint::TintHistory,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_switch_cooldown.in_set(AppSystems::TickTimers),
+            switch_color.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Colors unlocked by the player, in unlock order.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ColorPalette {
+    colors: Vec<GameColor>,
+    /// Minimal time between two switches
+    cooldown: Timer,
+}
+
+impl ColorPalette {
+    /// `cooldown` in sec.
+    pub fn new(colors: impl IntoIterator<Item = GameColor>, cooldown: f32) -> Self {
+        let mut timer = Timer::from_seconds(cooldown, TimerMode::Once);
+        timer.finish();
+
+        let mut palette = Self {
+            colors: Vec::new(),
+            cooldown: timer,
+        };
+        for color in colors {
+            palette.unlock(color);
+        }
+        palette
+    }
+
+    /// Return the unlocked colors.
+    pub fn colors(&self) -> &[GameColor] {
+        &self.colors
+    }
+
+    /// Add the color to the palette if not already unlocked.
+    pub fn unlock(&mut self, color: GameColor) {
+        if !self.colors.contains(&color) {
+            self.colors.push(color);
+        }
+    }
+
+    /// Return the tints to switch to, in order of preference.
+    ///
+    /// Each unlocked color of the tint is shifted to the next unlocked colors,
+    /// so a mixed tint stays mixed. Colors not unlocked, e.g. from timed paint,
+    /// are dropped.
+    fn next_tints(&self, tint: &Tint) -> Vec<Tint> {
+        let len = self.colors.len();
+        let positions = self
+            .colors
+            .iter()
+            .enumerate()
+            .filter(|(_, color)| tint.has_color(**color))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        if positions.is_empty() {
+            return self.colors.iter().copied().map(Tint::from_color).collect();
+        }
+
+        (1..len)
+            .map(|shift| {
+                positions
+                    .iter()
+                    .map(|index| self.colors[(index + shift) % len])
+                    .collect::<Tint>()
+            })
+            .filter(|next| next != tint)
+            .collect()
+    }
+}
+
+fn tick_switch_cooldown(time: Res<Time>, palettes: Query<&mut ColorPalette>) {
+    for mut palette in palettes {
+        palette.cooldown.tick(time.delta());
+    }
+}
+
+/// Switch to the next unlocked color.
+///
+/// Colors that would make a wall overlapping the player solid are skipped, the
+/// switch is rejected if all of them are.
+fn switch_color(
+    mut commands: Commands,
+    input: Res<ButtonInput<Action>>,
+    player: Single<
+        (
+            &mut ColorPalette,
+            &mut Tint,
+            &mut TintHistory,
+            &Transform,
+            &Collider,
+        ),
+        (With<Player>, Without<Dying>),
+    >,
+    walls: Query<&Tint, (With<Wall>, Without<Player>)>,
+    spatial_query: SpatialQuery,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+) {
+    let (mut palette, mut tint, mut history, transform, collider) = player.into_inner();
+    if !input.just_pressed(Action::SwitchColor)
+        || !palette.cooldown.is_finished()
+        || palette.colors.len() < 2
+    {
+        return;
+    }
+
+    let position = transform.translation.xy().adjust_precision();
+    let next_tints = palette.next_tints(&tint);
+    if next_tints.is_empty() {
+        return;
+    }
+    let Some(next_tint) = next_tints
+        .into_iter()
+        .find(|next| !overlaps_solid_wall(&spatial_query, collider, position, next, &walls))
+    else {
+        commands.trigger(TintRejected);
+        return;
+    };
+
+    *tint = next_tint;
+    history.clear();
+    palette.cooldown.reset();
+    commands.spawn(sound_effect(
+        audio_assets.paint_spray.clone(),
+        &audio_settings,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_keeps_mixed_tint() {
+        let palette = ColorPalette::new(
+            [
+                GameColor::Grey,
+                GameColor::Green,
+                GameColor::Brown,
+                GameColor::Red,
+            ],
+            0.5,
+        );
+        let tint = Tint::from(vec![GameColor::Green, GameColor::Brown]);
+
+        let next_tints = palette.next_tints(&tint);
+        assert_eq!(
+            next_tints.first(),
+            Some(&Tint::from(vec![GameColor::Brown, GameColor::Red]))
+        );
+        assert!(next_tints.iter().all(|next| next.get_colors().len() == 2));
+    }
+}
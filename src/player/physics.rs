@@ -1,5 +1,5 @@
 use avian2d::{
-    math::{AdjustPrecision, AsF32, Vector},
+    math::{AdjustPrecision, AsF32, Scalar, Vector},
     prelude::*,
 };
 use bevy::prelude::*;
@@ -12,6 +12,35 @@ use crate::{
 
 pub(super) fn plugin(_app: &mut App) {}
 
+/// Side of a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum WallSide {
+    Left,
+    Right,
+}
+
+impl WallSide {
+    /// Return the horizontal direction pointing toward the side.
+    pub fn direction(&self) -> Scalar {
+        match self {
+            Self::Left => -1.0,
+            Self::Right => 1.0,
+        }
+    }
+}
+
+/// Component indicating that a [`Player`] touches a solid wall on the given side.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[component(storage = "SparseSet")]
+pub struct TouchingWall(pub WallSide);
+
+/// Shape cast on the sides of the character to detect [`TouchingWall`].
+#[derive(Component)]
+pub struct WallCaster {
+    shape: Collider,
+    max_distance: Scalar,
+}
+
 /// Marker component indicating that a [`Player`] is grounded.
 #[derive(Component, Debug, Default)]
 #[component(storage = "SparseSet")]
@@ -29,6 +58,8 @@ pub struct CharacterPhysicsBundle {
 
     /// Shape cast used to detect ground contacts for [`Grounded`].
     ground_caster: ShapeCaster,
+    /// Shape cast used to detect wall contacts for [`TouchingWall`].
+    wall_caster: WallCaster,
 }
 
 impl CharacterPhysicsBundle {
@@ -38,6 +69,10 @@ impl CharacterPhysicsBundle {
         let mut caster_shape = collider.clone();
         caster_shape.set_scale(Vector::ONE * 0.99, 10);
 
+        // Shorter shape to not detect the ground and the ceiling
+        let mut wall_caster_shape = collider.clone();
+        wall_caster_shape.set_scale(Vector::new(0.99, 0.8), 10);
+
         Self {
             collider,
             body: RigidBody::Kinematic,
@@ -51,6 +86,10 @@ impl CharacterPhysicsBundle {
             ground_caster: ShapeCaster::new(caster_shape, Vector::ZERO, 0.0, Dir2::NEG_Y)
                 .with_max_distance(3.0)
                 .with_max_hits(7),
+            wall_caster: WallCaster {
+                shape: wall_caster_shape,
+                max_distance: 2.0,
+            },
         }
     }
 }
@@ -81,6 +120,69 @@ pub(super) fn update_grounded(
     }
 }
 
+/// Updates the [`TouchingWall`] status for character controllers.
+///
+/// Walls sharing a color with the character are intangible and are ignored.
+pub(super) fn update_touching_wall(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform, &WallCaster, &Tint), With<Player>>,
+    walls: Query<&Tint, With<Wall>>,
+    spatial_query: SpatialQuery,
+) {
+    for (entity, transform, caster, player_tint) in &query {
+        let origin = transform.translation.xy().adjust_precision();
+        let config = ShapeCastConfig::from_max_distance(caster.max_distance);
+        let filter =
+            SpatialQueryFilter::from_mask(GameLayer::Ground).with_excluded_entities([entity]);
+
+        let touches_wall = |direction: Dir2| {
+            spatial_query
+                .shape_hits(&caster.shape, origin, 0.0, direction, 7, &config, &filter)
+                .iter()
+                .any(|hit_data| {
+                    walls
+                        .get(hit_data.entity)
+                        .is_ok_and(|hit_tint| !player_tint.share_color_with(hit_tint))
+                })
+        };
+
+        if touches_wall(Dir2::NEG_X) {
+            commands.entity(entity).insert(TouchingWall(WallSide::Left));
+        } else if touches_wall(Dir2::X) {
+            commands
+                .entity(entity)
+                .insert(TouchingWall(WallSide::Right));
+        } else {
+            commands.entity(entity).remove::<TouchingWall>();
+        }
+    }
+}
+
+/// Return True if the collider at `position` overlaps a wall that is solid for the `tint`.
+///
+/// Walls sharing a color with the tint are intangible and are ignored. The collider
+/// is slightly shrunk so that resting against a wall is not an overlap.
+pub fn overlaps_solid_wall(
+    spatial_query: &SpatialQuery,
+    collider: &Collider,
+    position: Vector,
+    tint: &Tint,
+    walls: &Query<&Tint, (With<Wall>, Without<Player>)>,
+) -> bool {
+    let mut shape = collider.clone();
+    shape.set_scale(Vector::ONE * 0.9, 10);
+    let filter = SpatialQueryFilter::from_mask(GameLayer::Ground);
+
+    spatial_query
+        .shape_intersections(&shape, position, 0.0, &filter)
+        .into_iter()
+        .any(|wall| {
+            walls
+                .get(wall)
+                .is_ok_and(|wall_tint| !tint.share_color_with(wall_tint))
+        })
+}
+
 /// System to run the move and slide algorithm, updating the player's transform and velocity.
 ///
 /// This replaces Avian's default "position integration" that moves kinematic bodies based on their
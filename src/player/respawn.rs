@@ -0,0 +1,147 @@
+//! Death and respawn of the player.
+//!
+//! On [`PlayerDied`] the screen fades to black, the player is moved back to the
+//! last [`RespawnPoint`] and the screen fades back in.
+
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    ldtk::{GameColor, Tint},
+    player::{Player, timed_paint::TintHistory},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RespawnPoint>();
+
+    app.add_observer(kill_player);
+
+    app.add_systems(
+        Update,
+        (
+            tick_dying.in_set(AppSystems::TickTimers),
+            update_death_fade.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Duration of the whole death animation, the player respawns halfway through.
+const DEATH_DURATION: f32 = 0.8;
+
+/// Event triggered when the player dies.
+#[derive(Event, Debug)]
+pub struct PlayerDied;
+
+/// Position and tint restored when the player respawns.
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct RespawnPoint {
+    pub position: Vec2,
+    pub tint: Tint,
+}
+
+impl Default for RespawnPoint {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            tint: Tint::from_color(GameColor::Grey),
+        }
+    }
+}
+
+/// The player is dying and cannot move.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Dying {
+    timer: Timer,
+    respawned: bool,
+}
+
+/// Full screen overlay faded during the death animation.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct DeathFade;
+
+fn kill_player(
+    _: On<PlayerDied>,
+    mut commands: Commands,
+    mut player: Query<(Entity, &mut LinearVelocity), (With<Player>, Without<Dying>)>,
+) {
+    let Ok((entity, mut velocity)) = player.single_mut() else {
+        return;
+    };
+
+    velocity.0 = Vec2::ZERO;
+    commands.entity(entity).insert(Dying {
+        timer: Timer::from_seconds(DEATH_DURATION, TimerMode::Once),
+        respawned: false,
+    });
+
+    commands.spawn((
+        Name::new("Death Fade"),
+        DeathFade,
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.0)),
+        GlobalZIndex(1),
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+fn tick_dying(
+    time: Res<Time>,
+    mut commands: Commands,
+    respawn_point: Res<RespawnPoint>,
+    player: Single<
+        (
+            Entity,
+            &mut Dying,
+            &mut Transform,
+            &mut Tint,
+            &mut TintHistory,
+            &mut LinearVelocity,
+        ),
+        With<Player>,
+    >,
+    fades: Query<Entity, With<DeathFade>>,
+) {
+    let (entity, mut dying, mut transform, mut tint, mut history, mut velocity) =
+        player.into_inner();
+    dying.timer.tick(time.delta());
+
+    // Respawn while the screen is black
+    if !dying.respawned && dying.timer.elapsed() >= dying.timer.duration() / 2 {
+        dying.respawned = true;
+        transform.translation = respawn_point.position.extend(transform.translation.z);
+        *tint = respawn_point.tint.clone();
+        history.clear();
+        velocity.0 = Vec2::ZERO;
+    }
+
+    if dying.timer.is_finished() {
+        commands.entity(entity).remove::<Dying>();
+        for fade in &fades {
+            commands.entity(fade).despawn();
+        }
+    }
+}
+
+fn update_death_fade(
+    dying: Single<&Dying, With<Player>>,
+    mut fades: Query<&mut BackgroundColor, With<DeathFade>>,
+) {
+    let progress = dying.timer.fraction();
+    let alpha = 1.0 - (2.0 * progress - 1.0).abs();
+    for mut background in &mut fades {
+        background.0 = Color::BLACK.with_alpha(alpha);
+    }
+}
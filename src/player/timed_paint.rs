@@ -0,0 +1,204 @@
+//! Timed paint wearing off after a while.
+//!
+//! Picking up a timed droplet pushes the previous [`Tint`] on the player
+//! [`TintHistory`]. When the paint wears off, the player reverts to it. The
+//! remaining time is shown in the HUD.
+
+use std::time::Duration;
+
+use avian2d::{math::AdjustPrecision, prelude::*};
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    ldtk::{GameColor, Tint, wall::Wall},
+    player::{Player, embed::checked_tint, physics::overlaps_solid_wall, respawn::Dying},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        wear_off_paint
+            .in_set(AppSystems::TickTimers)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Stack of the tints covered by timed paint.
+///
+/// Only the top paint wears off, the ones below resume once it is gone.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct TintHistory(Vec<TimedPaint>);
+
+#[derive(Reflect, Debug)]
+struct TimedPaint {
+    /// Tint restored when the paint wears off
+    previous: Tint,
+    /// Color of the droplet the paint came from
+    color: GameColor,
+    timer: Timer,
+}
+
+impl TintHistory {
+    /// Cover `previous` with a `color` paint lasting `duration` sec.
+    pub fn push(&mut self, previous: Tint, color: GameColor, duration: f32) {
+        self.0.push(TimedPaint {
+            previous,
+            color,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        });
+    }
+
+    /// Restart the paint currently worn for `duration` sec.
+    ///
+    /// Return False if the paint currently worn is not of `color`, or if no
+    /// timed paint is worn.
+    pub fn refresh(&mut self, color: GameColor, duration: f32) -> bool {
+        let Some(paint) = self.0.last_mut().filter(|paint| paint.color == color) else {
+            return false;
+        };
+        paint.timer = Timer::from_seconds(duration, TimerMode::Once);
+        true
+    }
+
+    /// Forget all the timed paint, e.g. when the tint is permanently changed.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Return the tint under all the timed paint.
+    pub fn base_tint(&self) -> Option<&Tint> {
+        self.0.first().map(|paint| &paint.previous)
+    }
+
+    /// Return the timer of the paint currently worn.
+    pub fn current(&self) -> Option<&Timer> {
+        self.0.last().map(|paint| &paint.timer)
+    }
+
+    /// Tick the top paint and return the tint to restore if it wore off.
+    ///
+    /// The paint stays on the stack until [`Self::pop`] is called.
+    fn tick(&mut self, delta: Duration) -> Option<Tint> {
+        let paint = self.0.last_mut()?;
+        paint.timer.tick(delta);
+        paint.timer.is_finished().then(|| paint.previous.clone())
+    }
+
+    /// Remove the top paint, once it wore off.
+    fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    /// Keep the top paint `delay` sec more.
+    fn postpone(&mut self, delay: f32) {
+        if let Some(paint) = self.0.last_mut() {
+            paint.timer = Timer::from_seconds(delay, TimerMode::Once);
+        }
+    }
+}
+
+/// Delay before retrying a revert blocked by a wall in sec.
+const REVERT_RETRY_DELAY: f32 = 0.2;
+
+/// Revert the player to the tint under the paint that wore off.
+///
+/// A revert embedding the player in a wall is postponed until they leave it,
+/// instead of being rejected by [`guard_tint_change`](super::embed::guard_tint_change).
+/// The paint is only removed once the player wears the previous tint.
+pub(super) fn wear_off_paint(
+    time: Res<Time>,
+    players: Query<
+        (&mut TintHistory, &mut Tint, &Transform, &Collider),
+        (With<Player>, Without<Dying>),
+    >,
+    walls: Query<&Tint, (With<Wall>, Without<Player>)>,
+    spatial_query: SpatialQuery,
+) {
+    for (mut history, mut tint, transform, collider) in players {
+        let Some(previous) = history.tick(time.delta()) else {
+            continue;
+        };
+
+        if *tint == previous {
+            history.pop();
+            continue;
+        }
+
+        let position = transform.translation.xy().adjust_precision();
+        let checked = checked_tint(&tint, &previous, |tint| {
+            overlaps_solid_wall(&spatial_query, collider, position, tint, &walls)
+        });
+        match checked {
+            Some(_) => *tint = previous,
+            None => history.postpone(REVERT_RETRY_DELAY),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revert_in_stack_order() {
+        let mut history = TintHistory::default();
+        history.push(Tint::from_color(GameColor::Grey), GameColor::Green, 2.0);
+        history.push(Tint::from_color(GameColor::Green), GameColor::Brown, 1.0);
+
+        assert_eq!(
+            history.tick(Duration::from_secs(1)),
+            Some(Tint::from_color(GameColor::Green))
+        );
+        history.pop();
+        assert_eq!(history.tick(Duration::from_millis(500)), None);
+        assert_eq!(
+            history.tick(Duration::from_secs(2)),
+            Some(Tint::from_color(GameColor::Grey))
+        );
+        history.pop();
+        assert!(history.current().is_none());
+    }
+
+    #[test]
+    fn test_postpone_worn_off_paint() {
+        let mut history = TintHistory::default();
+        history.push(Tint::from_color(GameColor::Grey), GameColor::Green, 1.0);
+
+        assert!(history.tick(Duration::from_secs(1)).is_some());
+        history.postpone(0.5);
+        assert_eq!(history.tick(Duration::from_millis(250)), None);
+        assert!(history.tick(Duration::from_millis(250)).is_some());
+    }
+
+    #[test]
+    fn test_refresh_only_paint_of_same_color() {
+        let mut history = TintHistory::default();
+        history.push(Tint::from_color(GameColor::Grey), GameColor::Green, 1.0);
+        history.push(Tint::from_color(GameColor::Green), GameColor::Brown, 1.0);
+        history.tick(Duration::from_millis(500));
+
+        assert!(!history.refresh(GameColor::Green, 1.0));
+        assert_eq!(
+            history.tick(Duration::from_millis(500)),
+            Some(Tint::from_color(GameColor::Green))
+        );
+        assert!(history.refresh(GameColor::Brown, 1.0));
+        assert_eq!(history.tick(Duration::from_millis(500)), None);
+    }
+
+    #[test]
+    fn test_base_tint_is_under_all_paint() {
+        let mut history = TintHistory::default();
+        history.push(Tint::from_color(GameColor::Grey), GameColor::Green, 2.0);
+        history.push(Tint::from_color(GameColor::Green), GameColor::Brown, 1.0);
+
+        assert_eq!(
+            history.base_tint(),
+            Some(&Tint::from_color(GameColor::Grey))
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    render::render_resource::AsBindGroup,
+    shader::ShaderRef,
+    sprite_render::{Material2d, Material2dPlugin},
+};
 
 use crate::{
     ldtk::{GameColor, Tint},
@@ -6,13 +11,56 @@ use crate::{
 };
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(Material2dPlugin::<TintMaterial>::default());
+
     app.add_systems(Update, update_player_color);
 }
 
+/// Maximal number of colors drawn by a [`TintMaterial`].
+pub const MAX_TINT_COLORS: usize = 4;
+
+/// Material drawing each color of a [`Tint`] as diagonal stripes.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct TintMaterial {
+    #[uniform(0)]
+    colors: [LinearRgba; MAX_TINT_COLORS],
+    #[uniform(0)]
+    count: u32,
+}
+
+impl Material2d for TintMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/tint.wgsl".into()
+    }
+}
+
+impl From<&Tint> for TintMaterial {
+    fn from(tint: &Tint) -> Self {
+        let mut game_colors = tint.get_colors();
+        if game_colors.is_empty() {
+            warn!("Player should have a color. Use default to GameColor::White");
+            game_colors.push(GameColor::White);
+        }
+        // Keep the stripes in a stable order
+        game_colors.sort_by_key(|color| *color as u8);
+        game_colors.truncate(MAX_TINT_COLORS);
+
+        let mut colors = [LinearRgba::NONE; MAX_TINT_COLORS];
+        for (color, game_color) in colors.iter_mut().zip(&game_colors) {
+            *color = game_color.color().into();
+        }
+
+        Self {
+            colors,
+            count: game_colors.len() as u32,
+        }
+    }
+}
+
 #[derive(Bundle)]
 pub struct CharacterVisualBundle {
     mesh: Mesh2d,
-    material: MeshMaterial2d<ColorMaterial>,
+    material: MeshMaterial2d<TintMaterial>,
     tint: Tint,
 }
 
@@ -21,33 +69,22 @@ impl CharacterVisualBundle {
         shape: Capsule2d,
         color: GameColor,
         mut mesh_assets: ResMut<Assets<Mesh>>,
-        mut material_asets: ResMut<Assets<ColorMaterial>>,
+        mut material_asets: ResMut<Assets<TintMaterial>>,
     ) -> Self {
+        let tint = Tint::from_color(color);
         Self {
             mesh: Mesh2d(mesh_assets.add(shape)),
-            material: MeshMaterial2d(material_asets.add(color.color())),
-            tint: color.into(),
+            material: MeshMaterial2d(material_asets.add(TintMaterial::from(&tint))),
+            tint,
         }
     }
 }
 
 fn update_player_color(
-    players: Query<(&mut MeshMaterial2d<ColorMaterial>, &Tint), (Changed<Tint>, With<Player>)>,
-    mut material_asets: ResMut<Assets<ColorMaterial>>,
+    players: Query<(&mut MeshMaterial2d<TintMaterial>, &Tint), (Changed<Tint>, With<Player>)>,
+    mut material_asets: ResMut<Assets<TintMaterial>>,
 ) {
     for (mut material, tint) in players {
-        let game_color = match tint.get_colors()[..] {
-            [color] => color,
-            [] => {
-                warn!("Player should have a color. Use default to GameColor::White");
-                GameColor::White
-            }
-            [color, ..] => {
-                warn!("Player should have a single color.");
-                color
-            }
-        };
-
-        *material = MeshMaterial2d(material_asets.add(game_color.color()))
+        *material = MeshMaterial2d(material_asets.add(TintMaterial::from(tint)))
     }
 }
@@ -0,0 +1,146 @@
+//! Save and restore the game progress.
+//!
+//! The progress is written when the player changes room and when leaving the
+//! gameplay screen. It is restored at startup, for the menus showing it, and
+//! when entering the gameplay screen from the "Play" button of the main menu.
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use bevy_ecs_ldtk::EntityIid;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ldtk::{
+        GameColor, Tint,
+        entities::{
+            museum::{Inventory, Museum},
+            object::{CollectedObjects, ObjectType},
+        },
+        gridvania::{GridCoords, GridLevelSelection, GridvaniaLevels},
+        transition::RoomEntered,
+    },
+    player::{Player, palette::ColorPalette, spawn_character, timed_paint::TintHistory},
+    screens::Screen,
+    storage,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GameProgress>();
+
+    app.add_observer(mark_room_visited);
+
+    app.add_systems(Startup, load_progress);
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (load_progress, select_saved_room)
+            .chain()
+            .before(spawn_character),
+    );
+    app.add_systems(
+        Update,
+        save_progress
+            .run_if(in_state(Screen::Gameplay).and(resource_changed::<GridLevelSelection>)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), save_progress);
+    app.add_systems(
+        Last,
+        save_progress.run_if(in_state(Screen::Gameplay).and(on_message::<AppExit>)),
+    );
+}
+
+/// Storage key of the save file.
+const SAVE_KEY: &str = "save";
+
+/// Progress of the player in the world.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Default, Clone)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameProgress {
+    /// Last saved position of the player, `None` on a new game.
+    pub player_position: Option<Vec2>,
+    /// Colors of the player [`Tint`].
+    pub tint: Vec<GameColor>,
+    /// LDtk iid of the objects picked up by the player.
+    pub collected_objects: HashSet<String>,
+    /// Rooms the player has already been in.
+    pub visited_rooms: HashSet<GridCoords>,
+    /// Whether the player unlocked color mixing.
+    pub color_mixing: bool,
+    /// Colors unlocked in the player [`ColorPalette`].
+    pub palette: Vec<GameColor>,
+    /// Objects carried by the player, not yet displayed in the museum.
+    pub inventory: Vec<ObjectType>,
+    /// LDtk iid of the museum pedestals displaying their object.
+    pub museum: HashSet<String>,
+    /// Open state of the doors toggled by the player, by LDtk iid.
+    pub door_states: HashMap<String, bool>,
+}
+
+fn load_progress(
+    mut progress: ResMut<GameProgress>,
+    mut collected_objects: ResMut<CollectedObjects>,
+    mut inventory: ResMut<Inventory>,
+    mut museum: ResMut<Museum>,
+) {
+    *progress = storage::load(SAVE_KEY).unwrap_or_default();
+    collected_objects.0 = progress
+        .collected_objects
+        .iter()
+        .map(|iid| EntityIid::new(iid.clone()))
+        .collect();
+    inventory.0 = progress.inventory.clone();
+    museum.displayed = progress
+        .museum
+        .iter()
+        .map(|iid| EntityIid::new(iid.clone()))
+        .collect();
+}
+
+/// Select the room of the saved player position.
+fn select_saved_room(
+    progress: Res<GameProgress>,
+    mut grid_level_selection: ResMut<GridLevelSelection>,
+    levels: Option<Res<GridvaniaLevels>>,
+) {
+    if let Some(position) = progress.player_position {
+        // Rooms are selected by their origin cell
+        let coords = GridCoords::from_world_position(position);
+        let room = levels.as_ref().and_then(|levels| levels.room_at(coords));
+        *grid_level_selection = GridLevelSelection(room.map_or(coords, |room| room.origin));
+    }
+}
+
+fn mark_room_visited(trigger: On<RoomEntered>, mut progress: ResMut<GameProgress>) {
+    progress.visited_rooms.insert(trigger.to);
+}
+
+fn save_progress(
+    mut progress: ResMut<GameProgress>,
+    player: Option<Single<(&Transform, &Tint, &TintHistory, &ColorPalette), With<Player>>>,
+    collected_objects: Res<CollectedObjects>,
+    inventory: Res<Inventory>,
+    museum: Res<Museum>,
+    grid_level_selection: Res<GridLevelSelection>,
+) {
+    if let Some((transform, tint, history, palette)) = player.map(|p| p.into_inner()) {
+        progress.player_position = Some(transform.translation.truncate());
+        // Timed paint is not saved
+        progress.tint = history.base_tint().unwrap_or(tint).get_colors();
+        progress.palette = palette.colors().to_vec();
+    }
+    progress.collected_objects = collected_objects
+        .iter()
+        .map(|iid| iid.as_str().to_owned())
+        .collect();
+    progress.inventory = inventory.0.clone();
+    progress.museum = museum
+        .displayed
+        .iter()
+        .map(|iid| iid.as_str().to_owned())
+        .collect();
+    progress.visited_rooms.insert(grid_level_selection.0);
+
+    storage::store(SAVE_KEY, &*progress);
+}
@@ -2,7 +2,7 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{Pause, menus::Menu, screens::Screen};
+use crate::{AppSystems, Pause, input::Action, menus::Menu, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
     // Toggle pause on key press.
@@ -12,14 +12,20 @@ pub(super) fn plugin(app: &mut App) {
             (pause, spawn_pause_overlay, open_pause_menu).run_if(
                 in_state(Screen::Gameplay)
                     .and(in_state(Menu::None))
-                    .and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
+                    .and(input_just_pressed(Action::Pause)),
+            ),
+            (pause, spawn_pause_overlay, open_map_menu).run_if(
+                in_state(Screen::Gameplay)
+                    .and(in_state(Menu::None))
+                    .and(input_just_pressed(Action::Map)),
             ),
             close_menu.run_if(
                 in_state(Screen::Gameplay)
-                    .and(not(in_state(Menu::None)))
-                    .and(input_just_pressed(KeyCode::KeyP)),
+                    .and(in_state(Menu::Pause))
+                    .and(input_just_pressed(Action::Pause)),
             ),
-        ),
+        )
+            .in_set(AppSystems::Update),
     );
     app.add_systems(OnExit(Screen::Gameplay), (close_menu, unpause));
     app.add_systems(
@@ -54,6 +60,10 @@ fn open_pause_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Pause);
 }
 
+fn open_map_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Map);
+}
+
 fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
@@ -0,0 +1,80 @@
+//! Persistent user settings.
+//!
+//! The settings are loaded at startup and written back whenever one of them
+//! changes. Every field of [`Settings`] falls back to its default value, so new
+//! settings can be added without invalidating the files of older versions.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::AudioSettings, input::InputBindings, ldtk::transition::RoomTransitionSettings, storage,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    let settings = storage::load::<Settings>(SETTINGS_KEY).unwrap_or_default();
+    if settings.version > SETTINGS_VERSION {
+        warn!(
+            "Settings version {} is newer than the supported version {}.",
+            settings.version, SETTINGS_VERSION
+        );
+    }
+
+    app.insert_resource(settings.audio);
+    app.insert_resource(settings.input);
+    app.insert_resource(settings.transition);
+
+    app.add_systems(
+        Update,
+        save_settings.run_if(
+            (resource_changed::<AudioSettings>
+                .or(resource_changed::<InputBindings>)
+                .or(resource_changed::<RoomTransitionSettings>))
+            .and(not(resource_added::<AudioSettings>)),
+        ),
+    );
+}
+
+/// Storage key of the settings file.
+const SETTINGS_KEY: &str = "settings";
+
+/// Current version of the settings file.
+///
+/// Increase it when the meaning of an existing field changes.
+const SETTINGS_VERSION: u32 = 1;
+
+/// Content of the settings file.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+struct Settings {
+    version: u32,
+    audio: AudioSettings,
+    input: InputBindings,
+    transition: RoomTransitionSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            audio: AudioSettings::default(),
+            input: InputBindings::default(),
+            transition: RoomTransitionSettings::default(),
+        }
+    }
+}
+
+fn save_settings(
+    audio: Res<AudioSettings>,
+    input: Res<InputBindings>,
+    transition: Res<RoomTransitionSettings>,
+) {
+    let settings = Settings {
+        version: SETTINGS_VERSION,
+        audio: audio.clone(),
+        input: input.clone(),
+        transition: transition.clone(),
+    };
+
+    storage::store(SETTINGS_KEY, &settings);
+}
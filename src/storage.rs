@@ -0,0 +1,96 @@
+//! Persistent storage for small RON documents (saves, settings...).
+//!
+//! Native builds write one file per key in the user data directory, while Wasm
+//! builds use the browser local storage.
+
+use bevy::log::warn;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Read and deserialize the document stored under `key`.
+///
+/// Return `None` if there is nothing stored or if the document is not valid.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let contents = backend::read(key)?;
+
+    ron::from_str(&contents)
+        .inspect_err(|err| warn!("Could not deserialize '{key}' because of {err}"))
+        .ok()
+}
+
+/// Serialize `value` and store it under `key`.
+pub fn store<T: Serialize>(key: &str, value: &T) {
+    let contents = match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Could not serialize '{key}' because of {err}");
+            return;
+        }
+    };
+
+    if let Err(err) = backend::write(key, &contents) {
+        warn!("Could not write '{key}' because of {err}");
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod backend {
+    use std::path::PathBuf;
+
+    const APP_NAME: &str = "chroma";
+
+    pub fn read(key: &str) -> Option<String> {
+        std::fs::read_to_string(path(key)).ok()
+    }
+
+    pub fn write(key: &str, contents: &str) -> Result<(), String> {
+        let path = path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+
+        std::fs::write(path, contents).map_err(|err| err.to_string())
+    }
+
+    fn path(key: &str) -> PathBuf {
+        data_dir().join(format!("{key}.ron"))
+    }
+
+    /// Platform specific data directory, falling back to the working directory.
+    fn data_dir() -> PathBuf {
+        let env_dir = |var: &str| std::env::var_os(var).map(PathBuf::from);
+
+        let base = if cfg!(target_os = "windows") {
+            env_dir("APPDATA")
+        } else if cfg!(target_os = "macos") {
+            env_dir("HOME").map(|home| home.join("Library/Application Support"))
+        } else {
+            env_dir("XDG_DATA_HOME")
+                .or_else(|| env_dir("HOME").map(|home| home.join(".local/share")))
+        };
+
+        base.map(|dir| dir.join(APP_NAME)).unwrap_or_default()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod backend {
+    const KEY_PREFIX: &str = "chroma";
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        local_storage()?
+            .get_item(&format!("{KEY_PREFIX}.{key}"))
+            .ok()
+            .flatten()
+    }
+
+    pub fn write(key: &str, contents: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("Local storage is not available.")?
+            .set_item(&format!("{KEY_PREFIX}.{key}"), contents)
+            .map_err(|err| format!("{err:?}"))
+    }
+}
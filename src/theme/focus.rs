@@ -0,0 +1,225 @@
+//! Focus navigation between buttons.
+//!
+//! Hovering a button with the mouse focuses it, while the arrow keys, Tab and the
+//! gamepad d-pad move the focus between buttons in spawn order. Enter and the
+//! gamepad south button activate the focused button like a mouse click.
+
+use std::time::Duration;
+
+use bevy::{
+    camera::NormalizedRenderTarget,
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerId},
+    },
+    prelude::*,
+};
+
+use crate::AppSystems;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(assign_focus_order);
+    app.add_observer(focus_on_over);
+    app.add_observer(unfocus_on_out);
+
+    app.add_systems(
+        Update,
+        (navigate_with_keyboard, navigate_with_gamepad)
+            .in_set(AppSystems::RecordInput)
+            .in_set(FocusNavigation),
+    );
+}
+
+/// Systems moving and activating the focus, to disable while the keys are used
+/// for something else.
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FocusNavigation;
+
+/// A widget that can receive the focus.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Focusable {
+    /// Position in the navigation order, i.e. the spawn order.
+    order: u32,
+}
+
+/// Marker component of the focused widget.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub struct Focused;
+
+fn assign_focus_order(
+    add: On<Add, Focusable>,
+    mut focusables: Query<&mut Focusable>,
+    mut next_order: Local<u32>,
+) {
+    let Ok(mut focusable) = focusables.get_mut(add.entity) else {
+        return;
+    };
+
+    focusable.order = *next_order;
+    *next_order = next_order.wrapping_add(1);
+}
+
+fn focus_on_over(
+    over: On<Pointer<Over>>,
+    mut commands: Commands,
+    focusables: Query<(), (With<Focusable>, Without<Focused>)>,
+    focused: Query<Entity, With<Focused>>,
+) {
+    if focusables.contains(over.event_target()) {
+        set_focus(&mut commands, &focused, over.event_target());
+    }
+}
+
+fn unfocus_on_out(
+    out: On<Pointer<Out>>,
+    mut commands: Commands,
+    focused: Query<(), With<Focused>>,
+) {
+    if focused.contains(out.event_target()) {
+        commands.entity(out.event_target()).remove::<Focused>();
+    }
+}
+
+/// A widget whose first and last buttons are activated with the left and right keys,
+/// when one of its buttons is focused.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct HorizontalAdjust;
+
+fn navigate_with_keyboard(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    focusables: Query<(Entity, &Focusable)>,
+    focused: Query<Entity, With<Focused>>,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    adjusts: Query<(), With<HorizontalAdjust>>,
+) {
+    let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if keyboard.just_pressed(KeyCode::ArrowDown) || (keyboard.just_pressed(KeyCode::Tab) && !shift)
+    {
+        move_focus(&mut commands, &focusables, &focused, 1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) || (keyboard.just_pressed(KeyCode::Tab) && shift) {
+        move_focus(&mut commands, &focusables, &focused, -1);
+    }
+    if keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        activate_focused(&mut commands, &focused);
+    }
+
+    let left = keyboard.just_pressed(KeyCode::ArrowLeft);
+    let right = keyboard.just_pressed(KeyCode::ArrowRight);
+    if !(left || right) {
+        return;
+    }
+
+    // Activate the first or last button of the adjusted widget
+    for entity in &focused {
+        let Some(adjust) = parents
+            .iter_ancestors(entity)
+            .find(|ancestor| adjusts.contains(*ancestor))
+        else {
+            continue;
+        };
+
+        let mut buttons = children
+            .iter_descendants(adjust)
+            .filter_map(|descendant| focusables.get(descendant).ok())
+            .collect::<Vec<_>>();
+        buttons.sort_by_key(|(_, focusable)| focusable.order);
+
+        let button = if left {
+            buttons.first()
+        } else {
+            buttons.last()
+        };
+        if let Some((button, _)) = button {
+            commands.trigger(click(*button));
+        }
+    }
+}
+
+fn navigate_with_gamepad(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    focusables: Query<(Entity, &Focusable)>,
+    focused: Query<Entity, With<Focused>>,
+) {
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::DPadDown) {
+            move_focus(&mut commands, &focusables, &focused, 1);
+        }
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            move_focus(&mut commands, &focusables, &focused, -1);
+        }
+        if gamepad.just_pressed(GamepadButton::South) {
+            activate_focused(&mut commands, &focused);
+        }
+    }
+}
+
+/// Move the focus `offset` widgets forward in the navigation order, wrapping around.
+///
+/// Focus the first (or last) widget if nothing is focused.
+fn move_focus(
+    commands: &mut Commands,
+    focusables: &Query<(Entity, &Focusable)>,
+    focused: &Query<Entity, With<Focused>>,
+    offset: i32,
+) {
+    let mut ordered = focusables.iter().collect::<Vec<_>>();
+    if ordered.is_empty() {
+        return;
+    }
+    ordered.sort_by_key(|(_, focusable)| focusable.order);
+
+    let len = ordered.len() as i32;
+    let current = focused
+        .iter()
+        .next()
+        .and_then(|entity| ordered.iter().position(|(e, _)| *e == entity));
+    let next = match current {
+        Some(index) => (index as i32 + offset).rem_euclid(len),
+        None if offset >= 0 => 0,
+        None => len - 1,
+    };
+
+    set_focus(commands, focused, ordered[next as usize].0);
+}
+
+fn set_focus(commands: &mut Commands, focused: &Query<Entity, With<Focused>>, entity: Entity) {
+    for previous in focused.iter().filter(|previous| *previous != entity) {
+        commands.entity(previous).remove::<Focused>();
+    }
+    commands.entity(entity).insert(Focused);
+}
+
+/// Activate the focused widget.
+fn activate_focused(commands: &mut Commands, focused: &Query<Entity, With<Focused>>) {
+    for entity in focused {
+        commands.trigger(click(entity));
+    }
+}
+
+/// A [`Pointer<Click>`] on the entity, running the same observers as a mouse click.
+fn click(entity: Entity) -> Pointer<Click> {
+    Pointer::new(
+        PointerId::Mouse,
+        Location {
+            target: NormalizedRenderTarget::None {
+                width: 0,
+                height: 0,
+            },
+            position: Vec2::ZERO,
+        },
+        Click {
+            button: PointerButton::Primary,
+            hit: HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+            duration: Duration::ZERO,
+        },
+        entity,
+    )
+}
@@ -4,21 +4,22 @@ use rand::seq::IndexedRandom;
 use crate::{
     asset_collection::AudioAssets,
     audio::{AudioSettings, sound_effect},
+    theme::focus::Focused,
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_observer(apply_interaction_palette_on_over);
-    app.add_observer(apply_interaction_palette_on_out);
+    app.add_observer(apply_interaction_palette_on_focus);
+    app.add_observer(apply_interaction_palette_on_unfocus);
 
-    app.add_observer(apply_selection_markers_on_over);
-    app.add_observer(apply_selection_markers_on_out);
+    app.add_observer(apply_selection_markers_on_focus);
+    app.add_observer(apply_selection_markers_on_unfocus);
 
     app.add_observer(play_sound_effect_on_click);
-    app.add_observer(play_sound_effect_on_over);
+    app.add_observer(play_sound_effect_on_focus);
 }
 
-/// Change the [`TextColor`] based on the current [`Interaction`]` state.
-/// The color is chosen randomly on over.
+/// Change the [`TextColor`] based on the current [`Focused`] state.
+/// The color is chosen randomly on focus, i.e. on mouse over or focus navigation.
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
 pub struct InteractionPalette {
@@ -26,7 +27,7 @@ pub struct InteractionPalette {
     pub hovered: Vec<Color>,
 }
 
-/// Add `>` and `<` around the selected button when hovered.
+/// Add `>` and `<` around the selected button when focused.
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
 pub struct SelectionMarkerText {
@@ -34,11 +35,11 @@ pub struct SelectionMarkerText {
     pub base: String,
 }
 
-fn apply_interaction_palette_on_over(
-    over: On<Pointer<Over>>,
+fn apply_interaction_palette_on_focus(
+    add: On<Add, Focused>,
     mut palette_query: Query<(&InteractionPalette, &mut TextColor)>,
 ) {
-    let Ok((palette, mut text)) = palette_query.get_mut(over.event_target()) else {
+    let Ok((palette, mut text)) = palette_query.get_mut(add.entity) else {
         return;
     };
 
@@ -46,33 +47,33 @@ fn apply_interaction_palette_on_over(
     *text = palette.hovered.choose(rng).unwrap().to_owned().into();
 }
 
-fn apply_interaction_palette_on_out(
-    out: On<Pointer<Out>>,
+fn apply_interaction_palette_on_unfocus(
+    remove: On<Remove, Focused>,
     mut palette_query: Query<(&InteractionPalette, &mut TextColor)>,
 ) {
-    let Ok((palette, mut text)) = palette_query.get_mut(out.event_target()) else {
+    let Ok((palette, mut text)) = palette_query.get_mut(remove.entity) else {
         return;
     };
 
     *text = palette.none.into();
 }
 
-fn apply_selection_markers_on_over(
-    over: On<Pointer<Over>>,
+fn apply_selection_markers_on_focus(
+    add: On<Add, Focused>,
     mut marker_query: Query<(&SelectionMarkerText, &mut Text)>,
 ) {
-    let Ok((marker, mut text)) = marker_query.get_mut(over.event_target()) else {
+    let Ok((marker, mut text)) = marker_query.get_mut(add.entity) else {
         return;
     };
 
     text.0 = format!("> {} <", marker.base);
 }
 
-fn apply_selection_markers_on_out(
-    out: On<Pointer<Out>>,
+fn apply_selection_markers_on_unfocus(
+    remove: On<Remove, Focused>,
     mut marker_query: Query<(&SelectionMarkerText, &mut Text)>,
 ) {
-    let Ok((marker, mut text)) = marker_query.get_mut(out.event_target()) else {
+    let Ok((marker, mut text)) = marker_query.get_mut(remove.entity) else {
         return;
     };
 
@@ -91,8 +92,8 @@ fn play_sound_effect_on_click(
     ));
 }
 
-fn play_sound_effect_on_over(
-    _: On<Pointer<Over>>,
+fn play_sound_effect_on_focus(
+    _: On<Add, Focused>,
     audio_assets: If<Res<AudioAssets>>,
     mut commands: Commands,
     audio_settings: Res<AudioSettings>,
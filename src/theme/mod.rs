@@ -3,6 +3,7 @@
 // Unused utilities may trigger this lints undesirably.
 #![allow(dead_code)]
 
+pub mod focus;
 pub mod interaction;
 pub mod palette;
 pub mod widget;
@@ -17,8 +18,9 @@ use bevy::prelude::*;
 use crate::asset_collection::UiAssets;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin).add_systems(
-        Update,
-        widget::add_font_to_button.run_if(resource_exists::<UiAssets>),
-    );
+    app.add_plugins((focus::plugin, interaction::plugin))
+        .add_systems(
+            Update,
+            widget::add_font_to_button.run_if(resource_exists::<UiAssets>),
+        );
 }
@@ -11,6 +11,9 @@ pub const BROWN: Color = Color::srgb_u8(138, 88, 101);
 pub const RED: Color = Color::srgb_u8(182, 92, 95);
 pub const ORANGE: Color = Color::srgb_u8(188, 133, 99);
 
+/// Neutral color of the doors, solid for every tint
+pub const DOOR: Color = Color::srgb_u8(62, 53, 70);
+
 /// All biome colors
 pub const BIOME_COLORS: &[Color] = &[GREY, GREEN, BROWN, RED, ORANGE];
 
@@ -10,6 +10,7 @@ use bevy::{
 use crate::{
     asset_collection::UiAssets,
     theme::{
+        focus::Focusable,
         interaction::{InteractionPalette, SelectionMarkerText},
         palette::*,
     },
@@ -147,6 +148,7 @@ where
                     hovered: BIOME_COLORS.into(),
                 },
                 TextColor(BUTTON_TEXT),
+                Focusable::default(),
             ));
             if with_markers {
                 text_entity.insert(SelectionMarkerText { base: text });